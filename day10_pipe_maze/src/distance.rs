@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+
+use crate::{
+    maze::{connected_neighbours, parse_input, Maze, Position},
+    start::{replace_start_pipe, StartPipeError},
+};
+
+/// Breadth-first distances from the start tile, following pipe connections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceMap {
+    start: Position,
+    // distances[y][x], `None` for tiles that cannot be reached from the start
+    distances: Vec<Vec<Option<u32>>>,
+    farthest_distance: u32,
+    farthest_tiles: Vec<Position>,
+}
+
+impl DistanceMap {
+    /// Expects the start tile to be replaced with its pipe already.
+    pub fn new(start: Position, maze: &Maze) -> Self {
+        let mut distances: Vec<Vec<Option<u32>>> =
+            maze.iter().map(|row| vec![None; row.len()]).collect();
+        let mut farthest_distance = 0;
+        let mut farthest_tiles = vec![start];
+
+        distances[start.1][start.0] = Some(0);
+        let mut queue: VecDeque<(Position, u32)> = VecDeque::from([(start, 0)]);
+
+        while let Some((position, distance)) = queue.pop_front() {
+            if distance > farthest_distance {
+                farthest_distance = distance;
+                farthest_tiles.clear();
+            }
+            if distance == farthest_distance && position != start {
+                farthest_tiles.push(position);
+            }
+
+            for (_, (x, y)) in connected_neighbours(maze, position) {
+                if distances[y][x].is_none() {
+                    distances[y][x] = Some(distance + 1);
+                    queue.push_back(((x, y), distance + 1));
+                }
+            }
+        }
+
+        // tiles are visited row by row within a level, keep the report stable
+        farthest_tiles.sort_by_key(|&(x, y)| (y, x));
+
+        Self {
+            start,
+            distances,
+            farthest_distance,
+            farthest_tiles,
+        }
+    }
+
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn farthest_distance(&self) -> u32 {
+        self.farthest_distance
+    }
+
+    /// Every tile at the farthest distance, ordered by row then column.
+    pub fn farthest_tiles(&self) -> &[Position] {
+        &self.farthest_tiles
+    }
+
+    pub fn distances(&self) -> &Vec<Vec<Option<u32>>> {
+        &self.distances
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<u32> {
+        *self.distances.get(y)?.get(x)?
+    }
+}
+
+pub fn distance_map(input: &str) -> Result<DistanceMap, StartPipeError> {
+    let (start, mut maze) = parse_input(input);
+    replace_start_pipe(&mut maze, start)?;
+    Ok(DistanceMap::new(start, &maze))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        consts::INPUT,
+        maze::{EAST, NORTH, SOUTH, WEST},
+    };
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let input = r#"
+            .....
+            .S-7.
+            .|.|.
+            .L-J.
+            .....
+        "#;
+        let map = distance_map(input).unwrap();
+
+        assert_eq!(map.farthest_distance(), 4);
+        assert_eq!(map.farthest_tiles(), &[(3, 3)]);
+        assert_eq!(
+            map.distances()[1..4]
+                .iter()
+                .map(|row| row[1..4].to_vec())
+                .collect::<Vec<Vec<Option<u32>>>>(),
+            vec![
+                vec![Some(0), Some(1), Some(2)],
+                vec![Some(1), None, Some(3)],
+                vec![Some(2), Some(3), Some(4)],
+            ]
+        );
+    }
+
+    #[test]
+    fn it_ignores_pipes_outside_of_the_loop() {
+        let input = r#"
+            -L|F7
+            7S-7|
+            L|7||
+            -L-J|
+            L|-JF
+        "#;
+        let map = distance_map(input).unwrap();
+
+        assert_eq!(map.farthest_distance(), 4);
+        assert_eq!(map.get((0, 0)), None);
+        assert_eq!(map.get((4, 4)), None);
+        assert_eq!(map.get((1, 1)), Some(0));
+    }
+
+    #[test]
+    fn it_rejects_ambiguous_starts() {
+        let input = r#"
+            .|.
+            -S-
+            .|.
+        "#;
+
+        assert_eq!(
+            distance_map(input),
+            Err(StartPipeError::AmbiguousStart(vec![
+                NORTH, SOUTH, WEST, EAST
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_reports_every_farthest_tile() {
+        let input = r#"
+            S--7
+            |..|
+            L--J
+        "#;
+        let map = distance_map(input).unwrap();
+
+        assert_eq!(map.farthest_distance(), 5);
+        assert_eq!(map.farthest_tiles(), &[(3, 2)]);
+    }
+
+    #[test]
+    fn it_works_with_puzzle() {
+        assert_eq!(distance_map(INPUT).unwrap().farthest_distance(), 6956);
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+
+use utils::random::Random;
+
+use crate::maze::{
+    opposite, step, Direction, Position, DIRECTIONS, EAST, NORTH, PIPES, SOUTH, WEST,
+};
+
+// cell offsets around a cell, in clockwise order starting north
+const RING: [(i8, i8); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeConfig {
+    /// Number of tiles per row, at least 2.
+    pub width: usize,
+    /// Number of rows, at least 2.
+    pub height: usize,
+    /// Share of the grid the loop should try to enclose, between 0 and 1.
+    pub fill_ratio: f64,
+    /// Probability for a tile outside of the main loop to hold a junk pipe.
+    pub junk_ratio: f64,
+}
+
+/// A generated puzzle input along with its answers, known by construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedMaze {
+    pub input: String,
+    pub start: Position,
+    pub loop_length: u32,
+    pub enclosed_tiles: u32,
+}
+
+impl GeneratedMaze {
+    pub fn farthest_distance(&self) -> u32 {
+        self.loop_length / 2
+    }
+}
+
+type Cells = HashSet<(i64, i64)>;
+
+// Adding a cell keeps the region a topological disk (so its outline stays a
+// single simple loop) only when the part of the cell's border it shares with
+// the region is one connected arc containing at least one full edge.
+fn can_grow_into(cells: &Cells, (cx, cy): (i64, i64)) -> bool {
+    let occupied = RING.map(|(dx, dy)| cells.contains(&(cx + dx as i64, cy + dy as i64)));
+
+    // border pieces in clockwise order: edge N, corner NE, edge E, corner SE...
+    let pieces: [bool; 8] = std::array::from_fn(|i| {
+        if i % 2 == 0 {
+            occupied[i]
+        } else {
+            occupied[i - 1] || occupied[i] || occupied[(i + 1) % 8]
+        }
+    });
+
+    let has_edge = pieces.iter().step_by(2).any(|&piece| piece);
+    let arcs = (0..8)
+        .filter(|&i| pieces[i] && !pieces[(i + 7) % 8])
+        .count();
+    has_edge && arcs == 1
+}
+
+fn grow_region(random: &mut Random, config: &MazeConfig) -> Cells {
+    let cells_wide = config.width as i64 - 1;
+    let cells_high = config.height as i64 - 1;
+    let target = ((cells_wide * cells_high) as f64 * config.fill_ratio).max(1.0) as usize;
+
+    let mut cells: Cells = HashSet::new();
+    cells.insert((
+        random.below(cells_wide as u64) as i64,
+        random.below(cells_high as u64) as i64,
+    ));
+
+    let mut attempts = 0;
+    while cells.len() < target && attempts < target * 20 {
+        attempts += 1;
+
+        let mut candidates = cells
+            .iter()
+            .flat_map(|&(x, y)| DIRECTIONS.map(|(dx, dy)| (x + dx as i64, y + dy as i64)))
+            .filter(|&(x, y)| (0..cells_wide).contains(&x) && (0..cells_high).contains(&y))
+            .filter(|cell| !cells.contains(cell))
+            .collect::<Vec<(i64, i64)>>();
+        candidates.sort();
+        candidates.dedup();
+
+        let candidate = *random.choose(&candidates);
+        if can_grow_into(&cells, candidate) {
+            cells.insert(candidate);
+        }
+    }
+    cells
+}
+
+fn pipe_for(directions: &[Direction]) -> char {
+    PIPES
+        .iter()
+        .find(|pipe| directions.iter().all(|&d| pipe.has_opening(d)))
+        .unwrap()
+        .val
+}
+
+/// Generates a maze whose main loop is the outline of a randomly grown region
+/// of cells, each cell spanning four tiles at its corners. The loop length is
+/// the outline length and, by Pick's theorem, the number of enclosed tiles is
+/// `area - loop_length / 2 + 1`.
+pub fn generate(seed: u64, config: &MazeConfig) -> GeneratedMaze {
+    assert!(
+        config.width >= 2 && config.height >= 2,
+        "Maze should be at least 2x2"
+    );
+
+    let mut random = Random::new(seed);
+    let cells = grow_region(&mut random, config);
+
+    // openings of every loop tile, a tile sits at the corner of cells
+    let mut openings: Vec<Vec<Vec<Direction>>> = vec![vec![vec![]; config.width]; config.height];
+    let mut loop_length = 0;
+    for &(cx, cy) in cells.iter() {
+        let (x, y) = (cx as usize, cy as usize);
+        let sides = [
+            (NORTH, (x, y), (x + 1, y), EAST),
+            (SOUTH, (x, y + 1), (x + 1, y + 1), EAST),
+            (WEST, (x, y), (x, y + 1), SOUTH),
+            (EAST, (x + 1, y), (x + 1, y + 1), SOUTH),
+        ];
+        for (side, a, b, along) in sides {
+            if cells.contains(&(cx + side.0 as i64, cy + side.1 as i64)) {
+                continue;
+            }
+            openings[a.1][a.0].push(along);
+            openings[b.1][b.0].push(opposite(along));
+            loop_length += 1;
+        }
+    }
+
+    let main_loop = (0..config.height)
+        .flat_map(|y| (0..config.width).map(move |x| (x, y)))
+        .filter(|&(x, y)| !openings[y][x].is_empty())
+        .collect::<Vec<Position>>();
+    let start = *random.choose(&main_loop);
+    let main_loop = main_loop.into_iter().collect::<HashSet<Position>>();
+
+    let mut rows: Vec<String> = vec![];
+    for (y, row) in openings.iter().enumerate() {
+        let mut line = String::new();
+        for (x, tile_openings) in row.iter().enumerate() {
+            if (x, y) == start {
+                line.push('S');
+            } else if main_loop.contains(&(x, y)) {
+                line.push(pipe_for(tile_openings));
+            } else if random.chance(config.junk_ratio) {
+                // junk must never look like it connects to the start tile
+                let junk = PIPES
+                    .iter()
+                    .filter(|pipe| !pipe.is_start())
+                    .filter(|pipe| {
+                        pipe.directions
+                            .iter()
+                            .all(|&direction| step((x, y), direction) != Some(start))
+                    })
+                    .map(|pipe| pipe.val)
+                    .collect::<Vec<char>>();
+                line.push(*random.choose(&junk));
+            } else {
+                line.push('.');
+            }
+        }
+        rows.push(line);
+    }
+
+    let area = cells.len() as u32;
+    GeneratedMaze {
+        input: rows.join("\n"),
+        start,
+        loop_length,
+        enclosed_tiles: area + 1 - loop_length / 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::split_and_clean_input_into_lines;
+
+    use crate::{maze::parse_input, part1, part2, tracer::trace_loop};
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let config = MazeConfig {
+            width: 12,
+            height: 8,
+            fill_ratio: 0.6,
+            junk_ratio: 0.5,
+        };
+        let maze = generate(1, &config);
+
+        assert_eq!(maze, generate(1, &config));
+        assert_ne!(maze.input, generate(2, &config).input);
+
+        let lines = maze.input.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 8);
+        assert!(lines.iter().all(|line| line.len() == 12));
+        assert_eq!(lines[maze.start.1].chars().nth(maze.start.0), Some('S'));
+    }
+
+    #[test]
+    fn it_builds_a_single_loop_through_start() {
+        for seed in 0..200 {
+            let config = MazeConfig {
+                width: 3 + seed as usize % 10,
+                height: 3 + seed as usize % 7,
+                fill_ratio: 0.7,
+                junk_ratio: 0.3,
+            };
+            let maze = generate(seed, &config);
+            let (start, parsed) = parse_input(&maze.input);
+
+            assert_eq!(start, maze.start);
+            assert_eq!(
+                trace_loop(start, &parsed).map(|main_loop| main_loop.len() as u32),
+                Ok(maze.loop_length),
+                "seed {}:\n{}",
+                seed,
+                maze.input
+            );
+        }
+    }
+
+    #[test]
+    fn it_handles_the_smallest_maze() {
+        let config = MazeConfig {
+            width: 2,
+            height: 2,
+            fill_ratio: 1.0,
+            junk_ratio: 1.0,
+        };
+        let maze = generate(0, &config);
+
+        assert_eq!(maze.loop_length, 4);
+        assert_eq!(maze.enclosed_tiles, 0);
+    }
+
+    #[test]
+    fn it_matches_both_answers() {
+        for seed in 0..2000 {
+            let config = MazeConfig {
+                width: 2 + seed as usize % 14,
+                height: 2 + seed as usize % 9,
+                fill_ratio: [0.3, 0.6, 0.9][seed as usize % 3],
+                junk_ratio: [0.0, 0.4, 1.0][seed as usize % 3],
+            };
+            let maze = generate(seed, &config);
+
+            assert_eq!(
+                (part1::solve(&maze.input), part2::solve(&maze.input)),
+                (maze.farthest_distance(), maze.enclosed_tiles),
+                "seed {}:\n{}",
+                seed,
+                maze.input
+            );
+        }
+    }
+
+    #[test]
+    fn it_generates_squeezes_between_pipes() {
+        let config = MazeConfig {
+            width: 7,
+            height: 6,
+            fill_ratio: 0.6,
+            junk_ratio: 0.0,
+        };
+        let maze = generate(4, &config);
+
+        // the outside reaches down between the `7F` and `||` columns
+        // without a single tile to walk on
+        assert_eq!(
+            maze.input,
+            split_and_clean_input_into_lines(
+                r#"
+                .......
+                .S7F--7
+                .|||..|
+                .|LJ.FJ
+                FJ...L7
+                L-----J
+            "#
+            )
+            .join("\n")
+        );
+        assert_eq!(maze.enclosed_tiles, 6);
+        assert_eq!(part2::solve(&maze.input), 6);
+    }
+}
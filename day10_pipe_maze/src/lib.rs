@@ -1,3 +1,9 @@
 pub mod consts;
+pub mod distance;
+pub mod generator;
+pub mod maze;
 pub mod part1;
 pub mod part2;
+pub mod render;
+pub mod start;
+pub mod tracer;
@@ -0,0 +1,159 @@
+use utils::split_and_clean_input_into_lines;
+
+pub type Direction = (i8, i8);
+pub type Position = (usize, usize);
+
+pub const NORTH: Direction = (0, -1);
+pub const SOUTH: Direction = (0, 1);
+pub const WEST: Direction = (-1, 0);
+pub const EAST: Direction = (1, 0);
+pub const START: Direction = (0, 0);
+
+pub const DIRECTIONS: [Direction; 4] = [NORTH, SOUTH, WEST, EAST];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Pipe {
+    pub val: char,
+    pub directions: [Direction; 2],
+}
+
+impl Pipe {
+    pub fn get_next_direction(&self, direction: Direction) -> Direction {
+        let dir_a = self.directions[0];
+        let dir_b = self.directions[1];
+
+        // if we can continue in the same direction we'll take it
+        if direction == dir_a || direction == dir_b {
+            return direction;
+        }
+
+        // if we can't continue in the right direction then
+        // we'll need to take a left or right
+        if direction.0 == dir_a.0 || direction.1 == dir_a.1 {
+            return dir_b;
+        }
+        dir_a
+    }
+
+    pub fn is_start(&self) -> bool {
+        self.val == S
+    }
+
+    pub fn has_opening(&self, direction: Direction) -> bool {
+        self.directions.contains(&direction)
+    }
+
+    /// The opening left when entering through `entry`, `None` when the pipe
+    /// has no opening on that side.
+    pub fn get_exit(&self, entry: Direction) -> Option<Direction> {
+        match self.directions {
+            [a, b] if a == entry => Some(b),
+            [a, b] if b == entry => Some(a),
+            _ => None,
+        }
+    }
+}
+
+pub const PIPES: [Pipe; 7] = [
+    Pipe {
+        val: '|',
+        directions: [NORTH, SOUTH],
+    },
+    Pipe {
+        val: '-',
+        directions: [EAST, WEST],
+    },
+    Pipe {
+        val: 'L',
+        directions: [NORTH, EAST],
+    },
+    Pipe {
+        val: 'J',
+        directions: [NORTH, WEST],
+    },
+    Pipe {
+        val: '7',
+        directions: [WEST, SOUTH],
+    },
+    Pipe {
+        val: 'F',
+        directions: [EAST, SOUTH],
+    },
+    Pipe {
+        val: 'S',
+        directions: [START, START],
+    },
+];
+
+pub const G: char = '.';
+pub const S: char = 'S';
+
+pub type Maze<'a> = Vec<Vec<Option<&'a Pipe>>>;
+
+pub fn find_pipe(val: char) -> Option<&'static Pipe> {
+    PIPES.iter().find(|pipe| pipe.val == val)
+}
+
+pub fn parse_input(input: &str) -> (Position, Maze<'static>) {
+    let mut start_x = 0;
+    let mut start_y = 0;
+    let lines = split_and_clean_input_into_lines(input);
+
+    let mut maze: Maze = vec![];
+
+    for (y, line) in lines.iter().enumerate() {
+        let mut maze_row = vec![];
+        for (x, c) in line.chars().enumerate() {
+            if c == G {
+                maze_row.push(None);
+                continue;
+            }
+            if c == S {
+                start_x = x;
+                start_y = y;
+            }
+            maze_row.push(find_pipe(c));
+        }
+        maze.push(maze_row);
+    }
+    ((start_x, start_y), maze)
+}
+
+pub fn opposite(direction: Direction) -> Direction {
+    (-direction.0, -direction.1)
+}
+
+/// Moves one tile in the given direction, `None` when leaving the top or left edge.
+pub fn step((x, y): Position, direction: Direction) -> Option<Position> {
+    Some((
+        x.checked_add_signed(direction.0 as isize)?,
+        y.checked_add_signed(direction.1 as isize)?,
+    ))
+}
+
+pub fn get_pipe<'a>(maze: &Maze<'a>, (x, y): Position) -> Option<&'a Pipe> {
+    *maze.get(y)?.get(x)?
+}
+
+/// Neighbouring tiles that share an opening with the tile at `position`.
+/// The start tile has no opening, replace it with its pipe first.
+pub fn connected_neighbours(maze: &Maze, position: Position) -> Vec<(Direction, Position)> {
+    let pipe = match get_pipe(maze, position) {
+        Some(pipe) => pipe,
+        None => return vec![],
+    };
+
+    DIRECTIONS
+        .iter()
+        .filter(|&&direction| pipe.has_opening(direction))
+        .filter_map(|&direction| {
+            let neighbour = step(position, direction)?;
+            let neighbour_pipe = get_pipe(maze, neighbour)?;
+            if neighbour_pipe.has_opening(opposite(direction)) {
+                Some((direction, neighbour))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
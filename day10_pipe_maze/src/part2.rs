@@ -1,130 +1,11 @@
-use utils::split_and_clean_input_into_lines;
+use std::collections::HashSet;
 
-type Direction = (i8, i8);
+use crate::{
+    maze::{parse_input, Maze, Position},
+    tracer::trace_loop,
+};
 
-const NORTH: Direction = (0, -1);
-const SOUTH: Direction = (0, 1);
-const WEST: Direction = (-1, 0);
-const EAST: Direction = (1, 0);
-const START: Direction = (0, 0);
-
-#[derive(Debug)]
-struct Pipe {
-    pub val: char,
-    pub directions: [Direction; 2],
-}
-
-impl Pipe {
-    pub fn get_next_direction(&self, direction: Direction) -> Direction {
-        let dir_a = self.directions[0];
-        let dir_b = self.directions[1];
-
-        // if we can continue in the same direction we'll take it
-        if direction == dir_a || direction == dir_b {
-            return direction;
-        }
-
-        // if we can't continue in the right direction then
-        // we'll need to take a left or right
-        if direction.0 == dir_a.0 || direction.1 == dir_a.1 {
-            return dir_b;
-        }
-        dir_a
-    }
-}
-
-const PIPES: [Pipe; 7] = [
-    Pipe {
-        val: '|',
-        directions: [NORTH, SOUTH],
-    },
-    Pipe {
-        val: '-',
-        directions: [EAST, WEST],
-    },
-    Pipe {
-        val: 'L',
-        directions: [NORTH, EAST],
-    },
-    Pipe {
-        val: 'J',
-        directions: [NORTH, WEST],
-    },
-    Pipe {
-        val: '7',
-        directions: [WEST, SOUTH],
-    },
-    Pipe {
-        val: 'F',
-        directions: [EAST, SOUTH],
-    },
-    Pipe {
-        val: 'S',
-        directions: [START, START],
-    },
-];
-
-const G: char = '.';
-const S: char = 'S';
-
-type Maze<'a> = Vec<Vec<Option<&'a Pipe>>>;
-
-fn parse_input(input: &str) -> ((usize, usize), Maze) {
-    let mut start_x = 0;
-    let mut start_y = 0;
-    let lines = split_and_clean_input_into_lines(input);
-
-    let mut maze: Maze = vec![];
-
-    for y in 0..lines.len() {
-        let line = lines[y];
-        let mut maze_row = vec![];
-        for x in 0..line.len() {
-            let c = line.get(x..x + 1).unwrap().parse::<char>().unwrap();
-            if c == G {
-                maze_row.push(None);
-                continue;
-            }
-            if c == S {
-                start_x = x;
-                start_y = y;
-            }
-            maze_row.push(PIPES.iter().find(|pipe| pipe.val == c));
-        }
-        maze.push(maze_row);
-    }
-    ((start_x, start_y), maze)
-}
-
-fn get_initial_direction(start_x: usize, start_y: usize, maze: &Maze) -> Direction {
-    for (xo, yo) in [NORTH, SOUTH, WEST, EAST] {
-        let yc = (start_y as i64 + yo as i64) as usize;
-        let xc = (start_x as i64 + xo as i64) as usize;
-        let row = maze.get(yc);
-        if row.is_none() {
-            continue;
-        }
-        let cell = row.unwrap().get(xc);
-        if cell.is_none() {
-            continue;
-        }
-        let pipe = match cell.unwrap() {
-            Some(pipe) => *pipe,
-            None => continue,
-        };
-
-        for (px, py) in pipe.directions {
-            if xc as i32 + (px as i32) == start_x as i32
-                && yc as i32 + (py as i32) == start_y as i32
-            {
-                return (xo, yo);
-            }
-        }
-    }
-    panic!("Should never arrive here");
-}
-
-fn is_point_inside_polygon(point: (f64, f64), polygon: &Vec<(f64, f64)>) -> bool {
+fn is_point_inside_polygon(point: (f64, f64), polygon: &[(f64, f64)]) -> bool {
     let mut inside = false;
 
     for i in 0..polygon.len() {
@@ -144,13 +25,11 @@ fn is_point_inside_polygon(point: (f64, f64), polygon: &Vec<(f64, f64)>) -> bool
     inside
 }
 
-fn get_ground_vertices(maze: &Maze, polygon: &Vec<(f64, f64)>) -> Vec<(usize, usize)> {
+fn get_ground_vertices(maze: &Maze, main_loop: &HashSet<Position>) -> Vec<Position> {
     let mut vertices = vec![];
-    for y in 0..maze.len() {
-        let row = &maze[y];
-        for x in 0..row.len() {
-            let cell = row[x];
-            if cell.is_none() || !polygon.contains(&(x as f64, y as f64)) {
+    for (y, row) in maze.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if cell.is_none() || !main_loop.contains(&(x, y)) {
                 vertices.push((x, y));
             }
         }
@@ -158,34 +37,25 @@ fn get_ground_vertices(maze: &Maze, polygon: &Vec<(f64, f64)>) -> Vec<(usize, us
     vertices
 }
 
-pub fn solve(input: &str) -> u32 {
-    let ((start_x, start_y), maze) = parse_input(input);
-
-    let mut current_direction: Direction = get_initial_direction(start_x, start_y, &maze);
-
-    let mut x = start_x;
-    let mut y = start_y;
-
-    let mut polygon: Vec<(f64, f64)> = vec![];
-
-    loop {
-        x = (x as i64 + current_direction.0 as i64) as usize;
-        y = (y as i64 + current_direction.1 as i64) as usize;
-
-        let current_pipe = maze[y][x].unwrap();
-
-        polygon.push((x as f64, y as f64));
-        if current_pipe.val == S {
-            break;
-        }
+/// Every tile, ground or junk pipe, enclosed by the main loop.
+pub fn get_enclosed_tiles(maze: &Maze, main_loop: &[Position]) -> Vec<Position> {
+    let polygon = main_loop
+        .iter()
+        .map(|&(x, y)| (x as f64, y as f64))
+        .collect::<Vec<(f64, f64)>>();
+    let main_loop = main_loop.iter().copied().collect::<HashSet<Position>>();
+
+    get_ground_vertices(maze, &main_loop)
+        .into_iter()
+        .filter(|&(x, y)| is_point_inside_polygon((x as f64, y as f64), &polygon))
+        .collect()
+}
 
-        current_direction = current_pipe.get_next_direction(current_direction);
-    }
+pub fn solve(input: &str) -> u32 {
+    let (start, maze) = parse_input(input);
 
-    get_ground_vertices(&maze, &polygon)
-        .iter()
-        .filter(|&&(x, y)| is_point_inside_polygon((x as f64, y as f64), &polygon))
-        .count() as u32
+    let main_loop = trace_loop(start, &maze).unwrap();
+    get_enclosed_tiles(&maze, &main_loop).len() as u32
 }
 
 #[cfg(test)]
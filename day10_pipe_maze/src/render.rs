@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+
+use crate::{
+    maze::{parse_input, Maze, Position},
+    part2::get_enclosed_tiles,
+    tracer::{trace_loop, TraceError},
+};
+
+const INSIDE: char = 'I';
+const OUTSIDE: char = 'O';
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_LOOP: &str = "\x1b[1;33m";
+const ANSI_START: &str = "\x1b[1;31m";
+const ANSI_INSIDE: &str = "\x1b[1;32m";
+const ANSI_OUTSIDE: &str = "\x1b[2m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    Plain,
+    Ansi,
+}
+
+fn to_box_drawing(val: char) -> char {
+    match val {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => val,
+    }
+}
+
+fn paint(output: &mut String, c: char, colour: &str, mode: RenderMode) {
+    match mode {
+        RenderMode::Plain => output.push(c),
+        RenderMode::Ansi => {
+            output.push_str(colour);
+            output.push(c);
+            output.push_str(ANSI_RESET);
+        }
+    }
+}
+
+/// Draws the main loop with box-drawing characters, marking every other tile
+/// (ground or junk pipe) as either enclosed `I` or outside `O`.
+/// The start tile keeps its `S` so the loop can be followed from it.
+pub fn render_maze(
+    maze: &Maze,
+    start: Position,
+    main_loop: &[Position],
+    enclosed: &[Position],
+    mode: RenderMode,
+) -> String {
+    let main_loop = main_loop.iter().copied().collect::<HashSet<Position>>();
+    let enclosed = enclosed.iter().copied().collect::<HashSet<Position>>();
+
+    let mut output = String::new();
+    for (y, row) in maze.iter().enumerate() {
+        if y > 0 {
+            output.push('\n');
+        }
+        for (x, cell) in row.iter().enumerate() {
+            let position = (x, y);
+            if position == start {
+                paint(&mut output, 'S', ANSI_START, mode);
+            } else if main_loop.contains(&position) {
+                let val = cell.map(|pipe| pipe.val).unwrap();
+                paint(&mut output, to_box_drawing(val), ANSI_LOOP, mode);
+            } else if enclosed.contains(&position) {
+                paint(&mut output, INSIDE, ANSI_INSIDE, mode);
+            } else {
+                paint(&mut output, OUTSIDE, ANSI_OUTSIDE, mode);
+            }
+        }
+    }
+    output
+}
+
+pub fn render(input: &str, mode: RenderMode) -> Result<String, TraceError> {
+    let (start, maze) = parse_input(input);
+
+    let main_loop = trace_loop(start, &maze)?;
+    let enclosed = get_enclosed_tiles(&maze, &main_loop);
+    Ok(render_maze(&maze, start, &main_loop, &enclosed, mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let input = r#"
+            ...........
+            .S-------7.
+            .|F-----7|.
+            .||.....||.
+            .||.....||.
+            .|L-7.F-J|.
+            .|..|.|..|.
+            .L--J.L--J.
+            ...........
+        "#;
+        let expected = [
+            "OOOOOOOOOOO",
+            "OS───────┐O",
+            "O│┌─────┐│O",
+            "O││OOOOO││O",
+            "O││OOOOO││O",
+            "O│└─┐O┌─┘│O",
+            "O│II│O│II│O",
+            "O└──┘O└──┘O",
+            "OOOOOOOOOOO",
+        ];
+
+        assert_eq!(render(input, RenderMode::Plain), Ok(expected.join("\n")));
+    }
+
+    #[test]
+    fn it_squeezes_between_pipes() {
+        let input = r#"
+            ..........
+            .S------7.
+            .|F----7|.
+            .||OOOO||.
+            .||OOOO||.
+            .|L-7F-J|.
+            .|II||II|.
+            .L--JL--J.
+            ..........
+        "#;
+        let expected = [
+            "OOOOOOOOOO",
+            "OS──────┐O",
+            "O│┌────┐│O",
+            "O││OOOO││O",
+            "O││OOOO││O",
+            "O│└─┐┌─┘│O",
+            "O│II││II│O",
+            "O└──┘└──┘O",
+            "OOOOOOOOOO",
+        ];
+
+        assert_eq!(render(input, RenderMode::Plain), Ok(expected.join("\n")));
+    }
+
+    #[test]
+    fn it_colours_tiles() {
+        let input = r#"
+            ...
+            S7.
+            LJ.
+        "#;
+        let expected = [
+            "\x1b[2mO\x1b[0m\x1b[2mO\x1b[0m\x1b[2mO\x1b[0m",
+            "\x1b[1;31mS\x1b[0m\x1b[1;33m┐\x1b[0m\x1b[2mO\x1b[0m",
+            "\x1b[1;33m└\x1b[0m\x1b[1;33m┘\x1b[0m\x1b[2mO\x1b[0m",
+        ];
+
+        assert_eq!(render(input, RenderMode::Ansi), Ok(expected.join("\n")));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use crate::maze::{
+    find_pipe, get_pipe, opposite, step, Direction, Maze, Position, DIRECTIONS, PIPES,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartPipeError {
+    /// There is no `S` tile at the given position.
+    MissingStart(Position),
+    /// Fewer than two neighbours connect to `S`, the loop cannot be closed.
+    BrokenStart(Vec<Direction>),
+    /// More than two neighbours connect to `S`, the loop cannot be chosen.
+    AmbiguousStart(Vec<Direction>),
+}
+
+impl fmt::Display for StartPipeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStart((x, y)) => write!(f, "no start tile at ({}, {})", x, y),
+            Self::BrokenStart(connections) => write!(
+                f,
+                "start tile only connects to {} neighbour(s)",
+                connections.len()
+            ),
+            Self::AmbiguousStart(connections) => {
+                write!(f, "start tile connects to {} neighbours", connections.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for StartPipeError {}
+
+/// Infers which of `| - L J 7 F` is hidden under the start tile by looking at
+/// which of its four neighbours have an opening pointing back at it.
+pub fn infer_start_pipe(maze: &Maze, start: Position) -> Result<char, StartPipeError> {
+    match get_pipe(maze, start) {
+        Some(pipe) if pipe.is_start() => {}
+        _ => return Err(StartPipeError::MissingStart(start)),
+    }
+
+    let connections = DIRECTIONS
+        .into_iter()
+        .filter(|&direction| {
+            step(start, direction)
+                .and_then(|neighbour| get_pipe(maze, neighbour))
+                .is_some_and(|pipe| pipe.has_opening(opposite(direction)))
+        })
+        .collect::<Vec<Direction>>();
+
+    match connections.len() {
+        2 => Ok(PIPES
+            .iter()
+            .find(|pipe| connections.iter().all(|&d| pipe.has_opening(d)))
+            .unwrap()
+            .val),
+        0 | 1 => Err(StartPipeError::BrokenStart(connections)),
+        _ => Err(StartPipeError::AmbiguousStart(connections)),
+    }
+}
+
+/// Substitutes the inferred pipe for the start tile so that the maze no
+/// longer contains `S`, returning the pipe that was put in its place.
+pub fn replace_start_pipe(maze: &mut Maze, start: Position) -> Result<char, StartPipeError> {
+    let val = infer_start_pipe(maze, start)?;
+    maze[start.1][start.0] = find_pipe(val);
+    Ok(val)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        consts::INPUT,
+        maze::{parse_input, EAST, NORTH, SOUTH, WEST},
+    };
+
+    use super::*;
+
+    fn infer(input: &str) -> Result<char, StartPipeError> {
+        let (start, maze) = parse_input(input);
+        infer_start_pipe(&maze, start)
+    }
+
+    #[test]
+    fn it_works() {
+        let input = r#"
+            .....
+            .S-7.
+            .|.|.
+            .L-J.
+            .....
+        "#;
+        assert_eq!(infer(input), Ok('F'));
+
+        let input = r#"
+            ..F7.
+            .FJ|.
+            SJ.L7
+            |F--J
+            LJ...
+        "#;
+        assert_eq!(infer(input), Ok('F'));
+
+        let input = r#"
+            F-7
+            |.|
+            L-S
+        "#;
+        assert_eq!(infer(input), Ok('J'));
+    }
+
+    #[test]
+    fn it_replaces_the_start_tile() {
+        let (start, mut maze) = parse_input(
+            r#"
+            -L|F7
+            7S-7|
+            L|7||
+            -L-J|
+            L|-JF
+        "#,
+        );
+
+        assert_eq!(replace_start_pipe(&mut maze, start), Ok('F'));
+        assert_eq!(get_pipe(&maze, start).map(|pipe| pipe.val), Some('F'));
+        assert!(maze.iter().flatten().flatten().all(|pipe| !pipe.is_start()));
+    }
+
+    #[test]
+    fn it_rejects_broken_or_ambiguous_starts() {
+        let input = r#"
+            ...
+            .S.
+            ...
+        "#;
+        assert_eq!(infer(input), Err(StartPipeError::BrokenStart(vec![])));
+
+        let input = r#"
+            ...
+            .S-
+            ...
+        "#;
+        assert_eq!(infer(input), Err(StartPipeError::BrokenStart(vec![EAST])));
+
+        let input = r#"
+            .|.
+            -S-
+            .|.
+        "#;
+        assert_eq!(
+            infer(input),
+            Err(StartPipeError::AmbiguousStart(vec![
+                NORTH, SOUTH, WEST, EAST
+            ]))
+        );
+
+        let (_, maze) = parse_input(input);
+        assert_eq!(
+            infer_start_pipe(&maze, (0, 0)),
+            Err(StartPipeError::MissingStart((0, 0)))
+        );
+    }
+
+    #[test]
+    fn it_works_with_puzzle() {
+        assert_eq!(infer(INPUT), Ok('7'));
+    }
+}
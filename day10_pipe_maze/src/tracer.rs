@@ -0,0 +1,230 @@
+use std::fmt;
+
+use crate::{
+    maze::{find_pipe, get_pipe, opposite, step, Direction, Maze, Pipe, Position},
+    start::{infer_start_pipe, StartPipeError},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceError {
+    /// The start tile is missing or its pipe cannot be inferred.
+    Start(StartPipeError),
+    /// The loop leaves the grid when moving from `from` in `direction`.
+    OutOfBounds {
+        from: Position,
+        direction: Direction,
+    },
+    /// The loop runs into a ground tile.
+    Ground(Position),
+    /// The loop enters a pipe that has no opening facing back at it.
+    Disconnected {
+        position: Position,
+        direction: Direction,
+    },
+}
+
+impl From<StartPipeError> for TraceError {
+    fn from(err: StartPipeError) -> Self {
+        Self::Start(err)
+    }
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start(err) => write!(f, "{}", err),
+            Self::OutOfBounds {
+                from: (x, y),
+                direction,
+            } => write!(
+                f,
+                "loop leaves the grid from ({}, {}) heading {:?}",
+                x, y, direction
+            ),
+            Self::Ground((x, y)) => write!(f, "loop runs into ground at ({}, {})", x, y),
+            Self::Disconnected {
+                position: (x, y),
+                direction,
+            } => write!(
+                f,
+                "pipe at ({}, {}) does not connect when heading {:?}",
+                x, y, direction
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+fn get_start_pipe(maze: &Maze, start: Position) -> Result<&'static Pipe, TraceError> {
+    match get_pipe(maze, start) {
+        Some(pipe) if pipe.is_start() => Ok(find_pipe(infer_start_pipe(maze, start)?).unwrap()),
+        Some(pipe) => Ok(find_pipe(pipe.val).unwrap()),
+        None => Err(StartPipeError::MissingStart(start).into()),
+    }
+}
+
+/// Walks the main loop from the start tile, checking on every step that the
+/// tile entered has an opening facing the direction it was entered from.
+/// The start tile may still be `S` or already be replaced by its real pipe,
+/// it is the last position returned. Pipes that are not part of the loop are
+/// never looked at.
+pub fn trace_loop(start: Position, maze: &Maze) -> Result<Vec<Position>, TraceError> {
+    let start_pipe = get_start_pipe(maze, start)?;
+
+    let mut main_loop: Vec<Position> = vec![];
+    let mut position = start;
+    let mut direction = start_pipe.directions[0];
+
+    loop {
+        let next = step(position, direction)
+            .filter(|&(x, y)| maze.get(y).is_some_and(|row| x < row.len()))
+            .ok_or(TraceError::OutOfBounds {
+                from: position,
+                direction,
+            })?;
+
+        let pipe = if next == start {
+            start_pipe
+        } else {
+            get_pipe(maze, next).ok_or(TraceError::Ground(next))?
+        };
+
+        let next_direction =
+            pipe.get_exit(opposite(direction))
+                .ok_or(TraceError::Disconnected {
+                    position: next,
+                    direction,
+                })?;
+
+        main_loop.push(next);
+        if next == start {
+            break;
+        }
+
+        position = next;
+        direction = next_direction;
+    }
+    Ok(main_loop)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        consts::INPUT,
+        maze::{parse_input, EAST, NORTH, SOUTH, WEST},
+    };
+
+    use super::*;
+
+    fn trace(input: &str) -> Result<Vec<Position>, TraceError> {
+        let (start, maze) = parse_input(input);
+        trace_loop(start, &maze)
+    }
+
+    #[test]
+    fn it_works() {
+        let input = r#"
+            7-F7-
+            .FJ|7
+            SJLL7
+            |F--J
+            LJ.LJ
+        "#;
+        let main_loop = trace(input).unwrap();
+
+        assert_eq!(main_loop.len(), 16);
+        assert_eq!(main_loop.first(), Some(&(1, 2)));
+        assert_eq!(main_loop.last(), Some(&(0, 2)));
+    }
+
+    #[test]
+    fn it_reports_where_the_loop_breaks() {
+        let input = r#"
+            S-7
+            |.|
+            L-.
+        "#;
+        assert_eq!(trace(input), Err(TraceError::Ground((2, 2))));
+
+        let input = r#"
+            S-7
+            |.|
+            L|J
+        "#;
+        assert_eq!(
+            trace(input),
+            Err(TraceError::Disconnected {
+                position: (1, 2),
+                direction: WEST,
+            })
+        );
+
+        let input = r#"
+            S-7
+            |.|
+            |.|
+        "#;
+        assert_eq!(
+            trace(input),
+            Err(TraceError::OutOfBounds {
+                from: (2, 2),
+                direction: SOUTH,
+            })
+        );
+
+        let input = r#"
+            S--
+            |..
+        "#;
+        assert_eq!(
+            trace(input),
+            Err(TraceError::OutOfBounds {
+                from: (2, 0),
+                direction: EAST,
+            })
+        );
+
+        assert_eq!(
+            trace(".S."),
+            Err(TraceError::Start(StartPipeError::BrokenStart(vec![])))
+        );
+    }
+
+    #[test]
+    fn it_checks_an_already_replaced_start() {
+        let (_, mut maze) = parse_input(
+            r#"
+            S-7
+            |.|
+            L-J
+        "#,
+        );
+
+        maze[0][0] = find_pipe('|');
+        assert_eq!(
+            trace_loop((0, 0), &maze),
+            Err(TraceError::OutOfBounds {
+                from: (0, 0),
+                direction: NORTH,
+            })
+        );
+
+        maze[0][0] = find_pipe('-');
+        assert_eq!(
+            trace_loop((0, 0), &maze),
+            Err(TraceError::Disconnected {
+                position: (0, 0),
+                direction: NORTH,
+            })
+        );
+
+        maze[0][0] = find_pipe('F');
+        assert_eq!(trace_loop((0, 0), &maze).map(|l| l.len()), Ok(8));
+    }
+
+    #[test]
+    fn it_works_with_puzzle() {
+        assert_eq!(trace(INPUT).map(|main_loop| main_loop.len()), Ok(13912));
+    }
+}
@@ -0,0 +1,398 @@
+use std::{collections::BTreeSet, fmt};
+
+use crate::game::{Bag, Colour, Game};
+
+/// Belief about the bag before looking at any reveal, independent for every
+/// colour and truncated to `0..=max_per_colour`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prior {
+    Uniform,
+    /// Counts around a positive mean.
+    Poisson {
+        mean: f64,
+    },
+}
+
+impl Prior {
+    fn ln_weight(&self, count: u32, ln_factorials: &[f64]) -> f64 {
+        match self {
+            Self::Uniform => 0.0,
+            Self::Poisson { mean } => count as f64 * mean.ln() - ln_factorials[count as usize],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstimationConfig {
+    pub prior: Prior,
+    /// Largest number of cubes of a single colour the bag may hold.
+    pub max_per_colour: u32,
+    /// Colours known to be in the game besides the revealed ones.
+    pub colours: Vec<Colour>,
+    /// Most bags the grid may hold, every one of them is kept in memory.
+    pub max_bags: u64,
+}
+
+impl Default for EstimationConfig {
+    fn default() -> Self {
+        Self {
+            prior: Prior::Uniform,
+            max_per_colour: 20,
+            colours: vec![],
+            max_bags: 1 << 20,
+        }
+    }
+}
+
+/// Posterior probability of every bag, bags being cube counts listed in the
+/// order of `colours`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Posterior {
+    pub colours: Vec<Colour>,
+    pub bags: Vec<(Vec<u32>, f64)>,
+}
+
+impl Posterior {
+    pub fn probability(&self, bag: &Bag) -> f64 {
+        self.bags
+            .iter()
+            .find(|(counts, _)| {
+                self.colours
+                    .iter()
+                    .zip(counts.iter())
+                    .all(|(colour, count)| bag.get(colour).copied().unwrap_or(0) == *count)
+            })
+            .map_or(0.0, |&(_, probability)| probability)
+    }
+
+    /// The maximum a posteriori bag.
+    pub fn map_bag(&self) -> Bag {
+        let (counts, _) = self
+            .bags
+            .iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap();
+        self.colours
+            .iter()
+            .cloned()
+            .zip(counts.iter().copied())
+            .collect()
+    }
+
+    /// Probability of every count of the colour, indexed by count.
+    pub fn marginal(&self, colour: &Colour) -> Vec<f64> {
+        let Some(index) = self.colours.iter().position(|c| c == colour) else {
+            return vec![1.0];
+        };
+        let mut marginal: Vec<f64> = vec![];
+        for (counts, probability) in self.bags.iter() {
+            let count = counts[index] as usize;
+            if marginal.len() <= count {
+                marginal.resize(count + 1, 0.0);
+            }
+            marginal[count] += probability;
+        }
+        marginal
+    }
+
+    /// Equal-tailed interval holding `mass` of the colour's marginal.
+    pub fn credible_interval(&self, colour: &Colour, mass: f64) -> (u32, u32) {
+        let tail = (1.0 - mass) / 2.0;
+        let marginal = self.marginal(colour);
+        let quantile = |level: f64| -> u32 {
+            let mut cumulated = 0.0;
+            for (count, probability) in marginal.iter().enumerate() {
+                cumulated += probability;
+                // tolerate rounding when the whole mass is on a single count
+                if cumulated >= level - 1e-12 {
+                    return count as u32;
+                }
+            }
+            marginal.len() as u32 - 1
+        };
+        (quantile(tail), quantile(1.0 - tail))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EstimationError {
+    /// The Poisson prior needs a positive, finite mean.
+    InvalidMean { mean: f64 },
+    /// The grid holds more bags than the configuration allows, `None` when
+    /// the count does not even fit in a `u64`.
+    TooManyBags { bags: Option<u64>, max_bags: u64 },
+    /// No bag of the grid could have produced the reveals.
+    NoBagFits,
+}
+
+impl fmt::Display for EstimationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMean { mean } => write!(f, "Poisson mean {} is not positive", mean),
+            Self::TooManyBags {
+                bags: Some(bags),
+                max_bags,
+            } => write!(f, "{} bags to weigh, at most {} allowed", bags, max_bags),
+            Self::TooManyBags {
+                bags: None,
+                max_bags,
+            } => write!(f, "Too many bags to weigh, at most {} allowed", max_bags),
+            Self::NoBagFits => write!(f, "No bag could have produced the reveals"),
+        }
+    }
+}
+
+impl std::error::Error for EstimationError {}
+
+fn ln_choose(n: u32, k: u32, ln_factorials: &[f64]) -> f64 {
+    ln_factorials[n as usize] - ln_factorials[k as usize] - ln_factorials[(n - k) as usize]
+}
+
+// Every reveal is a handful whose size is uniform between 1 and the number
+// of cubes in the bag, its cubes drawn without replacement.
+fn ln_likelihood(game: &Game, colours: &[Colour], counts: &[u32], ln_factorials: &[f64]) -> f64 {
+    let cubes: u32 = counts.iter().sum();
+    let mut ln_likelihood = 0.0;
+
+    for reveal in game.reveals.iter() {
+        let handful = reveal.total();
+        // a record of zero cubes tells nothing about the bag
+        if handful == 0 {
+            continue;
+        }
+        if handful > cubes {
+            return f64::NEG_INFINITY;
+        }
+        ln_likelihood -= (cubes as f64).ln() + ln_choose(cubes, handful, ln_factorials);
+        for (colour, &count) in colours.iter().zip(counts.iter()) {
+            let shown = reveal.count(colour);
+            if shown > count {
+                return f64::NEG_INFINITY;
+            }
+            ln_likelihood += ln_choose(count, shown, ln_factorials);
+        }
+    }
+    ln_likelihood
+}
+
+/// Posterior over the bag's content given the game's reveals. Every bag of
+/// the grid is weighed, `(max_per_colour + 1)^colours` of them, so grids
+/// larger than `max_bags` are refused up front.
+pub fn estimate(game: &Game, config: &EstimationConfig) -> Result<Posterior, EstimationError> {
+    if let Prior::Poisson { mean } = config.prior {
+        if !(mean.is_finite() && mean > 0.0) {
+            return Err(EstimationError::InvalidMean { mean });
+        }
+    }
+
+    let colours = game
+        .colours()
+        .into_iter()
+        .chain(config.colours.iter().cloned())
+        .collect::<BTreeSet<Colour>>()
+        .into_iter()
+        .collect::<Vec<Colour>>();
+
+    let bags = u64::from(config.max_per_colour)
+        .checked_add(1)
+        .and_then(|side| side.checked_pow(colours.len() as u32));
+    if bags.is_none_or(|bags| bags > config.max_bags) {
+        return Err(EstimationError::TooManyBags {
+            bags,
+            max_bags: config.max_bags,
+        });
+    }
+
+    let largest_bag = config.max_per_colour as usize * colours.len().max(1);
+    let mut ln_factorials = vec![0.0; largest_bag + 1];
+    for n in 1..=largest_bag {
+        ln_factorials[n] = ln_factorials[n - 1] + (n as f64).ln();
+    }
+
+    let mut bags: Vec<(Vec<u32>, f64)> = vec![];
+    let mut counts = vec![0; colours.len()];
+    loop {
+        let ln_posterior = counts
+            .iter()
+            .map(|&count| config.prior.ln_weight(count, &ln_factorials))
+            .sum::<f64>()
+            + ln_likelihood(game, &colours, &counts, &ln_factorials);
+        if ln_posterior.is_finite() {
+            bags.push((counts.clone(), ln_posterior));
+        }
+
+        // next bag of the grid, odometer style
+        let Some(i) = counts
+            .iter()
+            .position(|&count| count < config.max_per_colour)
+        else {
+            break;
+        };
+        counts[i] += 1;
+        counts[..i].fill(0);
+    }
+
+    let max = bags
+        .iter()
+        .map(|&(_, ln)| ln)
+        .reduce(f64::max)
+        .ok_or(EstimationError::NoBagFits)?;
+    let normalizer = bags.iter().map(|&(_, ln)| (ln - max).exp()).sum::<f64>();
+    for (_, probability) in bags.iter_mut() {
+        *probability = (*probability - max).exp() / normalizer;
+    }
+
+    Ok(Posterior { colours, bags })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::parse_input;
+
+    use super::*;
+
+    fn game(record: &str) -> Game {
+        parse_input(record).unwrap().remove(0)
+    }
+
+    #[test]
+    fn it_works() {
+        let game = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let posterior = estimate(&game, &EstimationConfig::default()).unwrap();
+
+        let total = posterior.bags.iter().map(|(_, p)| p).sum::<f64>();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let map_bag = posterior.map_bag();
+        for (colour, minimum) in Bag::from(game.minimum_bag()) {
+            assert!(map_bag[&colour] >= minimum);
+            let (low, high) = posterior.credible_interval(&colour, 0.9);
+            assert!(minimum <= low && low <= map_bag[&colour] && map_bag[&colour] <= high);
+        }
+        assert!(posterior.probability(game.minimum_bag().counts()) > 0.0);
+        assert_eq!(
+            posterior.probability(&Bag::from([(Colour::from("red"), 3)])),
+            0.0
+        );
+    }
+
+    #[test]
+    fn it_matches_the_closed_form_for_a_single_colour() {
+        // a bag of n red cubes shows 2, 1 then 2 cubes with probability n^-3
+        let posterior = estimate(
+            &game("Game 1: 2 red; 1 red; 2 red"),
+            &EstimationConfig {
+                max_per_colour: 10,
+                ..EstimationConfig::default()
+            },
+        )
+        .unwrap();
+        let normalizer = (2..=10).map(|n| (n as f64).powi(-3)).sum::<f64>();
+        let marginal = posterior.marginal(&Colour::from("red"));
+
+        assert_eq!(marginal.len(), 11);
+        for (n, probability) in marginal.iter().enumerate().skip(2) {
+            assert!((probability - (n as f64).powi(-3) / normalizer).abs() < 1e-12);
+        }
+        assert_eq!(posterior.map_bag(), Bag::from([(Colour::from("red"), 2)]));
+        assert_eq!(
+            posterior.credible_interval(&Colour::from("red"), 0.5),
+            (2, 3)
+        );
+    }
+
+    #[test]
+    fn it_uses_the_prior_and_known_colours() {
+        let game = game("Game 1: 1 red; 1 red");
+        let config = EstimationConfig {
+            prior: Prior::Poisson { mean: 6.0 },
+            max_per_colour: 15,
+            colours: vec![Colour::from("blue")],
+            ..EstimationConfig::default()
+        };
+        let posterior = estimate(&game, &config).unwrap();
+
+        // never seeing blue pulls its count down, the prior keeps red up
+        let map_bag = posterior.map_bag();
+        assert_eq!(posterior.colours.len(), 2);
+        assert!(map_bag[&Colour::from("red")] > 1);
+        assert!(map_bag[&Colour::from("blue")] < map_bag[&Colour::from("red")]);
+
+        let config = EstimationConfig {
+            max_per_colour: 3,
+            ..EstimationConfig::default()
+        };
+        assert_eq!(
+            estimate(&parse_input("Game 1: 4 red").unwrap()[0], &config),
+            Err(EstimationError::NoBagFits)
+        );
+    }
+
+    #[test]
+    fn it_skips_empty_reveals() {
+        let config = EstimationConfig {
+            max_per_colour: 10,
+            ..EstimationConfig::default()
+        };
+        let with_empty = estimate(&game("Game 1: 2 red; 0 purple; 1 red"), &config).unwrap();
+        let known_purple = EstimationConfig {
+            colours: vec![Colour::from("purple")],
+            ..config.clone()
+        };
+        let without = estimate(&game("Game 1: 2 red; 1 red"), &known_purple).unwrap();
+
+        assert_eq!(with_empty.colours, without.colours);
+        for ((a, p), (b, q)) in with_empty.bags.iter().zip(without.bags.iter()) {
+            assert_eq!(a, b);
+            assert!((p - q).abs() < 1e-12);
+        }
+        assert!(estimate(&game("Game 1: 0 red"), &config).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_invalid_means() {
+        let game = game("Game 1: 1 red");
+        for mean in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            let config = EstimationConfig {
+                prior: Prior::Poisson { mean },
+                ..EstimationConfig::default()
+            };
+            assert!(matches!(
+                estimate(&game, &config),
+                Err(EstimationError::InvalidMean { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn it_refuses_grids_too_large() {
+        let game = game("Game 1: 1 red, 1 green, 1 blue, 1 purple, 1 teal, 1 pink");
+
+        assert_eq!(
+            estimate(&game, &EstimationConfig::default()),
+            Err(EstimationError::TooManyBags {
+                bags: Some(21u64.pow(6)),
+                max_bags: 1 << 20,
+            })
+        );
+        let config = EstimationConfig {
+            max_per_colour: u32::MAX,
+            ..EstimationConfig::default()
+        };
+        assert_eq!(
+            estimate(&game, &config),
+            Err(EstimationError::TooManyBags {
+                bags: None,
+                max_bags: 1 << 20,
+            })
+        );
+        let config = EstimationConfig {
+            max_per_colour: 3,
+            ..EstimationConfig::default()
+        };
+        assert_eq!(
+            estimate(&game, &config).unwrap().bags.len(),
+            3u64.pow(6) as usize
+        );
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt,
+    sync::Arc,
+};
+
+use utils::split_and_clean_input_into_lines;
+
+use crate::minimum_bag::MinimumBag;
+
+/// A cube colour, any name goes. Colours compare by name; parsing interns
+/// them so every cube of a record shares the same allocation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Colour(Arc<str>);
+
+impl Colour {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Colour {
+    fn from(name: &str) -> Self {
+        Self(Arc::from(name))
+    }
+}
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Interner {
+    names: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, name: &str) -> Colour {
+        if let Some(interned) = self.names.get(name) {
+            return Colour(interned.clone());
+        }
+        let interned: Arc<str> = Arc::from(name);
+        self.names.insert(interned.clone());
+        Colour(interned)
+    }
+}
+
+/// Cube counts per colour, colours not listed count as zero.
+pub type Bag = BTreeMap<Colour, u32>;
+
+/// A handful of cubes shown from the bag, in the order they were recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reveal {
+    pub cubes: Vec<(Colour, u32)>,
+}
+
+impl Reveal {
+    /// Cubes of the colour in the handful, a colour listed more than once
+    /// being one handful of their sum: `10 red, 3 red` shows 13 red cubes.
+    pub fn count(&self, colour: &Colour) -> u32 {
+        self.cubes
+            .iter()
+            .filter(|(c, _)| c == colour)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.cubes.iter().map(|(_, count)| count).sum()
+    }
+
+    /// The same cubes sorted by colour, a colour listed twice merged.
+    pub fn canonical(&self) -> Self {
+        let mut cubes: Vec<(Colour, u32)> = vec![];
+        for (colour, count) in self.cubes.iter() {
+            match cubes.iter_mut().find(|(c, _)| c == colour) {
+                Some((_, total)) => *total += count,
+                None => cubes.push((colour.clone(), *count)),
+            }
+        }
+        cubes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Self { cubes }
+    }
+}
+
+impl fmt::Display for Reveal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cubes = self
+            .cubes
+            .iter()
+            .map(|(colour, count)| format!("{} {}", count, colour))
+            .collect::<Vec<String>>();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub reveals: Vec<Reveal>,
+}
+
+impl Game {
+    /// Every colour revealed at least once, even with a count of zero.
+    pub fn colours(&self) -> BTreeSet<Colour> {
+        self.reveals
+            .iter()
+            .flat_map(|reveal| reveal.cubes.iter().map(|(colour, _)| colour.clone()))
+            .collect()
+    }
+
+    /// Whether every reveal fits in the bag. Entries of a colour listed more
+    /// than once in a reveal are added up before comparing, unlike the
+    /// original solution which checked every entry on its own.
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.reveals.iter().all(|reveal| {
+            reveal
+                .cubes
+                .iter()
+                .all(|(colour, _)| reveal.count(colour) <= bag.get(colour).copied().unwrap_or(0))
+        })
+    }
+
+    /// The fewest cubes of each revealed colour the bag could have held,
+    /// repeated entries of a colour in a reveal added up.
+    pub fn minimum_bag(&self) -> MinimumBag {
+        self.minimum_bag_over(&[])
+    }
+
+    /// The minimum bag, also listing the given colours even when the game
+    /// never showed them.
+    pub fn minimum_bag_over(&self, colours: &[Colour]) -> MinimumBag {
+        let mut bag = MinimumBag::empty(colours);
+        for reveal in self.reveals.iter() {
+            for (colour, _) in reveal.cubes.iter() {
+                bag.require(colour, reveal.count(colour));
+            }
+        }
+        bag
+    }
+
+    pub fn canonical(&self) -> Self {
+        Self {
+            id: self.id,
+            reveals: self
+                .reveals
+                .iter()
+                .map(|reveal| reveal.canonical())
+                .collect(),
+        }
+    }
+}
+
+/// Prints the record as `Game N: a red, b blue; ...`, in the recorded order
+/// of reveals and cubes. Print `canonical()` for a normalized record.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reveals = self
+            .reveals
+            .iter()
+            .map(|reveal| reveal.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "Game {}: {}", self.id, reveals.join("; "))
+    }
+}
+
+/// One record per line, parsed back by `parse_input`.
+pub fn print_games(games: &[Game], canonical: bool) -> String {
+    games
+        .iter()
+        .map(|game| {
+            if canonical {
+                game.canonical().to_string()
+            } else {
+                game.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGameError {
+    /// Line of the game, counting from 1 at the first game.
+    pub line: usize,
+    /// The piece of the record that could not be read.
+    pub text: String,
+}
+
+impl fmt::Display for ParseGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Could not read {:?} on line {}", self.text, self.line)
+    }
+}
+
+impl std::error::Error for ParseGameError {}
+
+fn parse_game(raw_game: &str, interner: &mut Interner) -> Result<Game, String> {
+    let (game_meta, sets_data) = raw_game.split_once(':').ok_or(raw_game)?;
+    let id = game_meta
+        .trim()
+        .strip_prefix("Game ")
+        .and_then(|id| id.trim().parse::<u32>().ok())
+        .ok_or(game_meta)?;
+
+    let reveals = sets_data
+        .split(';')
+        .map(|raw_set| {
+            let cubes = raw_set
+                .split(',')
+                .map(|raw_cubes| {
+                    let (count, colour) = raw_cubes.trim().split_once(' ').ok_or(raw_cubes)?;
+                    let count = count.parse::<u32>().map_err(|_| raw_cubes)?;
+                    Ok((interner.intern(colour.trim()), count))
+                })
+                .collect::<Result<Vec<(Colour, u32)>, &str>>()?;
+            Ok(Reveal { cubes })
+        })
+        .collect::<Result<Vec<Reveal>, &str>>()
+        .map_err(|text| text.trim().to_string())?;
+
+    Ok(Game { id, reveals })
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Game>, ParseGameError> {
+    let mut interner = Interner::default();
+    split_and_clean_input_into_lines(input)
+        .iter()
+        .enumerate()
+        .map(|(i, raw_game)| {
+            parse_game(raw_game, &mut interner).map_err(|text| ParseGameError { line: i + 1, text })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::random::Random;
+
+    use crate::consts::INPUT;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let games = parse_input("Game 7: 3 purple, 2 red; 1 red, 4 teal; 0 purple").unwrap();
+        let game = &games[0];
+
+        assert_eq!(game.id, 7);
+        assert_eq!(game.reveals.len(), 3);
+        assert_eq!(
+            game.colours()
+                .iter()
+                .map(|c| c.name())
+                .collect::<Vec<&str>>(),
+            vec!["purple", "red", "teal"]
+        );
+        assert_eq!(
+            game.minimum_bag().counts(),
+            &Bag::from([
+                (Colour::from("purple"), 3),
+                (Colour::from("red"), 2),
+                (Colour::from("teal"), 4),
+            ])
+        );
+
+        let bag = Bag::from([(Colour::from("purple"), 3), (Colour::from("red"), 2)]);
+        assert!(!game.is_possible(&bag));
+        let bag = Bag::from([
+            (Colour::from("purple"), 3),
+            (Colour::from("red"), 2),
+            (Colour::from("teal"), 9),
+        ]);
+        assert!(game.is_possible(&bag));
+    }
+
+    #[test]
+    fn it_adds_up_a_colour_listed_twice_in_a_reveal() {
+        let game = &parse_input("Game 1: 10 red, 3 red; 2 blue, 4 blue").unwrap()[0];
+        let red = Colour::from("red");
+
+        assert_eq!(game.reveals[0].count(&red), 13);
+        assert!(!game.is_possible(&Bag::from([(red.clone(), 12), (Colour::from("blue"), 6)])));
+        assert!(game.is_possible(&Bag::from([(red.clone(), 13), (Colour::from("blue"), 6)])));
+        assert_eq!(
+            game.minimum_bag().counts(),
+            &Bag::from([(Colour::from("blue"), 6), (red, 13)])
+        );
+    }
+
+    #[test]
+    fn it_interns_colours() {
+        let mut interner = Interner::default();
+        let a = interner.intern("red");
+        let b = interner.intern("red");
+
+        assert!(Arc::ptr_eq(&a.0, &b.0));
+        assert_ne!(a, interner.intern("blue"));
+    }
+
+    #[test]
+    fn it_reports_parse_errors() {
+        assert_eq!(
+            parse_input("Game 1: 3 red\nGame 2: 3 red, blue"),
+            Err(ParseGameError {
+                line: 2,
+                text: "blue".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_input("Game x: 3 red").map_err(|error| error.to_string()),
+            Err("Could not read \"Game x\" on line 1".to_string())
+        );
+    }
+
+    #[test]
+    fn it_prints_records() {
+        let record = "Game 3:  4 red,2 blue ;1 red, 3 green, 1 red";
+        let game = &parse_input(record).unwrap()[0];
+
+        assert_eq!(
+            game.to_string(),
+            "Game 3: 4 red, 2 blue; 1 red, 3 green, 1 red"
+        );
+        assert_eq!(
+            game.canonical().to_string(),
+            "Game 3: 2 blue, 4 red; 3 green, 2 red"
+        );
+
+        let games = parse_input(INPUT).unwrap();
+        assert_eq!(print_games(&games, false), INPUT.trim());
+        assert_eq!(parse_input(&print_games(&games, true)).unwrap().len(), 100);
+    }
+
+    #[test]
+    fn it_round_trips() {
+        let names = ["red", "green", "blue", "purple", "teal", "dark-orange"];
+        let mut random = Random::new(2023);
+
+        for _ in 0..500 {
+            let games = (1..=random.between(1, 5))
+                .map(|id| Game {
+                    id: id as u32 * random.between(1, 1000) as u32,
+                    reveals: (0..random.between(1, 6))
+                        .map(|_| Reveal {
+                            cubes: (0..random.between(1, 4))
+                                .map(|_| {
+                                    let colour = Colour::from(*random.choose(&names));
+                                    (colour, random.below(25) as u32)
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                })
+                .collect::<Vec<Game>>();
+
+            assert_eq!(parse_input(&print_games(&games, false)), Ok(games.clone()));
+
+            let canonical = games
+                .iter()
+                .map(|game| game.canonical())
+                .collect::<Vec<Game>>();
+            assert_eq!(
+                parse_input(&print_games(&games, true)),
+                Ok(canonical.clone())
+            );
+            assert_eq!(print_games(&canonical, true), print_games(&games, true));
+        }
+    }
+}
@@ -1,3 +1,8 @@
 pub mod consts;
+pub mod estimation;
+pub mod game;
+pub mod minimum_bag;
 pub mod part1;
 pub mod part2;
+pub mod query;
+pub mod simulator;
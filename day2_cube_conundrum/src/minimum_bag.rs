@@ -0,0 +1,104 @@
+use crate::game::{Bag, Colour};
+
+/// How the power of a minimum bag treats colours with no cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerPolicy {
+    /// Product of every count, a single missing colour makes it zero.
+    Strict,
+    /// Product of the counts of colours seen at least once, zero when no
+    /// cube was seen at all.
+    ObservedOnly,
+}
+
+/// The fewest cubes of each colour a bag needs to explain a game. Colours
+/// never seen are kept with a count of zero rather than left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MinimumBag {
+    counts: Bag,
+}
+
+impl MinimumBag {
+    /// A bag with no cube of any of the colours.
+    pub fn empty(colours: &[Colour]) -> Self {
+        Self {
+            counts: colours.iter().map(|colour| (colour.clone(), 0)).collect(),
+        }
+    }
+
+    /// Makes room for `count` cubes of the colour.
+    pub fn require(&mut self, colour: &Colour, count: u32) {
+        let minimum = self.counts.entry(colour.clone()).or_default();
+        *minimum = (*minimum).max(count);
+    }
+
+    pub fn count(&self, colour: &Colour) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    /// Every colour of the bag with its count, zeros included.
+    pub fn counts(&self) -> &Bag {
+        &self.counts
+    }
+
+    /// Number of cubes in the bag.
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    pub fn power(&self, policy: PowerPolicy) -> u64 {
+        let counts = self.counts.values().map(|&count| count as u64);
+        match policy {
+            PowerPolicy::Strict => counts.product(),
+            PowerPolicy::ObservedOnly => {
+                let observed = counts.filter(|&count| count > 0).collect::<Vec<u64>>();
+                if observed.is_empty() {
+                    return 0;
+                }
+                observed.into_iter().product()
+            }
+        }
+    }
+}
+
+impl From<MinimumBag> for Bag {
+    fn from(minimum_bag: MinimumBag) -> Self {
+        minimum_bag.counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let (red, green, blue) = (
+            Colour::from("red"),
+            Colour::from("green"),
+            Colour::from("blue"),
+        );
+        let mut bag = MinimumBag::empty(&[red.clone(), green.clone(), blue.clone()]);
+        bag.require(&red, 4);
+        bag.require(&red, 2);
+        bag.require(&blue, 6);
+
+        assert_eq!(bag.count(&red), 4);
+        assert_eq!(bag.count(&green), 0);
+        assert_eq!(bag.counts().len(), 3);
+        assert_eq!(bag.total(), 10);
+        assert_eq!(bag.power(PowerPolicy::Strict), 0);
+        assert_eq!(bag.power(PowerPolicy::ObservedOnly), 24);
+
+        bag.require(&green, 2);
+        assert_eq!(bag.power(PowerPolicy::Strict), 48);
+        assert_eq!(bag.power(PowerPolicy::ObservedOnly), 48);
+    }
+
+    #[test]
+    fn it_has_no_power_without_cubes() {
+        let bag = MinimumBag::empty(&[Colour::from("red")]);
+
+        assert_eq!(bag.power(PowerPolicy::Strict), 0);
+        assert_eq!(bag.power(PowerPolicy::ObservedOnly), 0);
+    }
+}
@@ -22,118 +22,32 @@
     Determine which games would have been possible if the bag had been loaded with only 12 red cubes, 13 green cubes, and 14 blue cubes. What is the sum of the IDs of those games?
 */
 
-use std::collections::HashMap;
+use crate::game::{parse_input, Bag};
 
-use utils::split_and_clean_input_into_lines;
-
-#[derive(Debug)]
-struct Game {
-    id: u32,
-    sets: Vec<GameSet>,
-}
-
-#[derive(Debug)]
-struct GameSet {
-    cube_results: Vec<CubeResult>,
-}
-
-#[derive(Debug, Eq, PartialEq, Hash)]
-pub enum CubeColor {
-    RED,
-    BLUE,
-    GREEN,
-}
-
-#[derive(Debug)]
-pub struct CubeResult {
-    color: CubeColor,
-    count: u32,
-}
-
-impl CubeResult {
-    pub fn new(count: u32, color: CubeColor) -> Self {
-        Self { color, count }
-    }
-}
-
-fn map_color_str_to_cube_color(color: &str) -> CubeColor {
-    match color {
-        "red" => CubeColor::RED,
-        "blue" => CubeColor::BLUE,
-        "green" => CubeColor::GREEN,
-        _ => panic!("Should never happen"),
-    }
-}
-
-fn parse_input(input: &str) -> Vec<Game> {
-    split_and_clean_input_into_lines(input)
+/// Sum of the IDs of the games that fit in the bag.
+pub fn cube_conundrum(input: &str, bag: &Bag) -> u32 {
+    parse_input(input)
+        .unwrap()
         .iter()
-        .map(|raw_game| {
-            let (game_meta, sets_data) = raw_game.trim().split_once(':').unwrap();
-
-            let game_id = game_meta.split_once(' ').unwrap().1.parse::<u32>().unwrap();
-
-            let sets = sets_data
-                .split(';')
-                .map(|raw_set| {
-                    let cube_results = raw_set
-                        .split(',')
-                        .map(|raw_balls_data| {
-                            let (ball_count_str, color) =
-                                raw_balls_data.trim().split_once(' ').unwrap();
-                            let cube_color = map_color_str_to_cube_color(color);
-                            let ball_count = ball_count_str.parse::<u32>().unwrap();
-
-                            CubeResult {
-                                count: ball_count,
-                                color: cube_color,
-                            }
-                        })
-                        .collect::<Vec<CubeResult>>();
-
-                    GameSet { cube_results }
-                })
-                .collect::<Vec<GameSet>>();
-
-            Game { id: game_id, sets }
-        })
-        .collect::<Vec<Game>>()
-}
-
-type ExpectedCubeResults = HashMap<CubeColor, u32>;
-
-pub fn cube_conundrum(input: &str, expected_cube_results: ExpectedCubeResults) -> u32 {
-    let games = parse_input(input);
-
-    let mut game_id_sum: u32 = 0;
-    for game in games {
-        let mut game_is_possible = true;
-        for set in game.sets {
-            for cube_result in set.cube_results {
-                let expected_cube_result = expected_cube_results.get(&cube_result.color).unwrap();
-                if cube_result.count > *expected_cube_result {
-                    game_is_possible = false;
-                    break;
-                }
-            }
-            if !game_is_possible {
-                break;
-            }
-        }
-        if game_is_possible {
-            game_id_sum += game.id;
-        }
-    }
-
-    game_id_sum
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::consts::INPUT;
+    use crate::{consts::INPUT, game::Colour};
 
     use super::*;
 
+    fn puzzle_bag() -> Bag {
+        Bag::from([
+            (Colour::from("red"), 12),
+            (Colour::from("green"), 13),
+            (Colour::from("blue"), 14),
+        ])
+    }
+
     #[test]
     fn it_works() {
         let input = r#"
@@ -144,19 +58,33 @@ mod tests {
             Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
         "#;
 
-        let mut expected_cube_result = HashMap::new();
-        expected_cube_result.insert(CubeColor::BLUE, 14);
-        expected_cube_result.insert(CubeColor::RED, 12);
-        expected_cube_result.insert(CubeColor::GREEN, 13);
-        assert_eq!(cube_conundrum(input, expected_cube_result), 8);
+        assert_eq!(cube_conundrum(input, &puzzle_bag()), 8);
+    }
+
+    #[test]
+    fn it_works_with_any_colour() {
+        let input = r#"
+            Game 1: 3 purple, 2 red; 1 red
+            Game 2: 4 purple
+            Game 3: 1 red, 1 teal
+        "#;
+        let bag = Bag::from([(Colour::from("purple"), 3), (Colour::from("red"), 2)]);
+
+        assert_eq!(cube_conundrum(input, &bag), 1);
+    }
+
+    #[test]
+    fn it_adds_up_a_colour_listed_twice_in_a_reveal() {
+        let input = r#"
+            Game 1: 10 red, 3 red
+            Game 2: 6 red, 6 red; 13 green
+        "#;
+
+        assert_eq!(cube_conundrum(input, &puzzle_bag()), 2);
     }
 
     #[test]
     fn it_works_for_puzzle() {
-        let mut expected_cube_result = HashMap::new();
-        expected_cube_result.insert(CubeColor::BLUE, 14);
-        expected_cube_result.insert(CubeColor::RED, 12);
-        expected_cube_result.insert(CubeColor::GREEN, 13);
-        assert_eq!(cube_conundrum(INPUT, expected_cube_result), 2256);
+        assert_eq!(cube_conundrum(INPUT, &puzzle_bag()), 2256);
     }
 }
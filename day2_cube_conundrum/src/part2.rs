@@ -41,106 +41,23 @@
     The power of a set of cubes is equal to the numbers of red, green, and blue cubes multiplied together. The power of the minimum set of cubes in game 1 is 48. In games 2-5 it was 12, 1560, 630, and 36, respectively. Adding up these five powers produces the sum 2286.
 */
 
-use utils::split_and_clean_input_into_lines;
-
-#[derive(Debug)]
-struct Game {
-    sets: Vec<GameSet>,
-}
-
-impl Game {
-    pub fn get_power(&self) -> u32 {
-        let mut max_red_count: u32 = 1;
-        let mut max_blue_count: u32 = 1;
-        let mut max_green_count: u32 = 1;
-
-        for set in &self.sets {
-            if set.blue_count.is_some() {
-                let blue_count = set.blue_count.unwrap();
-                if blue_count > max_blue_count {
-                    max_blue_count = blue_count;
-                }
-            }
-            if set.red_count.is_some() {
-                let red_count = set.red_count.unwrap();
-                if red_count > max_red_count {
-                    max_red_count = red_count;
-                }
-            }
-            if set.green_count.is_some() {
-                let green_count = set.green_count.unwrap();
-                if green_count > max_green_count {
-                    max_green_count = green_count;
-                }
-            }
-        }
-
-        max_red_count * max_green_count * max_blue_count
-    }
-}
-
-#[derive(Debug)]
-struct GameSet {
-    red_count: Option<u32>,
-    blue_count: Option<u32>,
-    green_count: Option<u32>,
-}
-
-#[derive(Debug, Eq, PartialEq, Hash)]
-pub enum CubeColor {
-    RED,
-    BLUE,
-    GREEN,
-}
-
-fn parse_input(input: &str) -> Vec<Game> {
-    split_and_clean_input_into_lines(input)
+use crate::{
+    game::{parse_input, Colour},
+    minimum_bag::PowerPolicy,
+};
+
+/// Sum of the powers of every game's minimum bag over the given colours.
+pub fn cube_conundrum_with(input: &str, colours: &[Colour], policy: PowerPolicy) -> u64 {
+    parse_input(input)
+        .unwrap()
         .iter()
-        .map(|raw_game| {
-            let (_, sets_data) = raw_game.trim().split_once(':').unwrap();
-
-            let sets = sets_data
-                .split(';')
-                .map(|raw_set| {
-                    let mut red_count: Option<u32> = Option::None;
-                    let mut blue_count: Option<u32> = Option::None;
-                    let mut green_count: Option<u32> = Option::None;
-
-                    for raw_balls_data in raw_set.split(',') {
-                        let (ball_count_str, color) =
-                            raw_balls_data.trim().split_once(' ').unwrap();
-                        let ball_count = ball_count_str.parse::<u32>().unwrap();
-
-                        match color {
-                            "blue" => blue_count = Some(ball_count),
-                            "green" => green_count = Some(ball_count),
-                            "red" => red_count = Some(ball_count),
-                            _ => panic!("Should never happen"),
-                        };
-                    }
-
-                    GameSet {
-                        red_count,
-                        blue_count,
-                        green_count,
-                    }
-                })
-                .collect::<Vec<GameSet>>();
-
-            Game { sets }
-        })
-        .collect::<Vec<Game>>()
+        .map(|game| game.minimum_bag_over(colours).power(policy))
+        .sum()
 }
 
-pub fn cube_conundrum(input: &str) -> u32 {
-    let games = parse_input(input);
-
-    let mut game_id_sum: u32 = 0;
-    for game in games {
-        game_id_sum += game.get_power();
-    }
-
-    game_id_sum
+pub fn cube_conundrum(input: &str) -> u64 {
+    let colours = ["red", "green", "blue"].map(Colour::from);
+    cube_conundrum_with(input, &colours, PowerPolicy::ObservedOnly)
 }
 
 #[cfg(test)]
@@ -162,6 +79,32 @@ mod tests {
         assert_eq!(cube_conundrum(input), 2286);
     }
 
+    #[test]
+    fn it_works_with_any_colour() {
+        let input = r#"
+            Game 1: 3 purple, 2 red; 1 red
+            Game 2: 4 purple, 1 teal, 2 red; 5 teal
+        "#;
+
+        assert_eq!(cube_conundrum(input), 6 + 40);
+    }
+
+    #[test]
+    fn it_does_not_invent_cubes() {
+        let input = r#"
+            Game 1: 3 blue, 4 red; 1 red, 6 blue
+            Game 2: 2 green
+        "#;
+        let colours = ["red", "green", "blue"].map(Colour::from);
+
+        assert_eq!(cube_conundrum(input), 24 + 2);
+        assert_eq!(cube_conundrum_with(input, &colours, PowerPolicy::Strict), 0);
+        assert_eq!(
+            cube_conundrum_with(input, &colours[..1], PowerPolicy::Strict),
+            24
+        );
+    }
+
     #[test]
     fn it_works_for_puzzle() {
         assert_eq!(cube_conundrum(INPUT), 74229);
@@ -0,0 +1,392 @@
+//! A small constraint language over cube counts, e.g.
+//! `red <= 12 && green <= 13 && (blue <= 14 || total <= 30)`.
+//!
+//! Identifiers are colour names, `total` is the number of cubes of every
+//! colour. Comparisons use `<`, `<=`, `>`, `>=`, `==` and `!=`, and combine
+//! with `!`, `&&`, `||` and parentheses. Colours that were not seen count as
+//! zero.
+
+use std::{fmt, str::FromStr};
+
+use crate::game::{Colour, Game, Reveal};
+
+/// What the values of a query are read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Every reveal of the game matches, the puzzle's possibility check.
+    EveryReveal,
+    /// At least one reveal of the game matches.
+    AnyReveal,
+    /// The game's minimum bag matches, `total` being the size of that bag.
+    Game,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn holds(&self, left: u32, right: u32) -> bool {
+        match self {
+            Self::Less => left < right,
+            Self::LessOrEqual => left <= right,
+            Self::Greater => left > right,
+            Self::GreaterOrEqual => left >= right,
+            Self::Equal => left == right,
+            Self::NotEqual => left != right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Total,
+    Colour(Colour),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    Compare(Operand, Comparison, u32),
+    Not(Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    /// Evaluates the expression, `count` giving the number of cubes of a
+    /// colour and `total` the number of cubes overall.
+    pub fn evaluate(&self, count: &impl Fn(&Colour) -> u32, total: u32) -> bool {
+        match self {
+            Self::Compare(operand, comparison, value) => {
+                let left = match operand {
+                    Operand::Total => total,
+                    Operand::Colour(colour) => count(colour),
+                };
+                comparison.holds(left, *value)
+            }
+            Self::Not(expression) => !expression.evaluate(count, total),
+            Self::And(left, right) => left.evaluate(count, total) && right.evaluate(count, total),
+            Self::Or(left, right) => left.evaluate(count, total) || right.evaluate(count, total),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseQueryError {
+    /// Byte offset in the query where reading failed.
+    pub position: usize,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected {} at {}", self.expected, self.position)
+    }
+}
+
+impl std::error::Error for ParseQueryError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    Number(u32),
+    Comparison(Comparison),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+fn tokenize(query: &str) -> Result<Vec<(usize, Token)>, ParseQueryError> {
+    let chars = query.char_indices().collect::<Vec<(usize, char)>>();
+    let mut tokens: Vec<(usize, Token)> = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (position, c) = chars[i];
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let error = ParseQueryError {
+            position,
+            expected: "a colour, a number or an operator",
+        };
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_alphanumeric() || c == '_' {
+            let end = chars[i..]
+                .iter()
+                .position(|&(_, c)| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .map_or(chars.len(), |length| i + length);
+            let word = chars[i..end].iter().map(|&(_, c)| c).collect::<String>();
+            let token = match word.parse::<u32>() {
+                Ok(number) => Token::Number(number),
+                Err(_) if c.is_ascii_digit() => return Err(error),
+                Err(_) => Token::Identifier(word),
+            };
+            tokens.push((position, token));
+            i = end;
+            continue;
+        }
+
+        let (token, length) = match (c, next) {
+            ('<', Some('=')) => (Token::Comparison(Comparison::LessOrEqual), 2),
+            ('>', Some('=')) => (Token::Comparison(Comparison::GreaterOrEqual), 2),
+            ('=', Some('=')) => (Token::Comparison(Comparison::Equal), 2),
+            ('!', Some('=')) => (Token::Comparison(Comparison::NotEqual), 2),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('<', _) => (Token::Comparison(Comparison::Less), 1),
+            ('>', _) => (Token::Comparison(Comparison::Greater), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            _ => return Err(error),
+        };
+        tokens.push((position, token));
+        i += length;
+    }
+
+    Ok(tokens)
+}
+
+// recursive descent, `||` binding looser than `&&`, itself looser than `!`
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(_, token)| token)
+    }
+
+    fn error(&self, expected: &'static str) -> ParseQueryError {
+        ParseQueryError {
+            position: self
+                .tokens
+                .get(self.index)
+                .map_or(self.end, |&(position, _)| position),
+            expected,
+        }
+    }
+
+    fn or(&mut self) -> Result<Expression, ParseQueryError> {
+        let mut expression = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.index += 1;
+            expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Result<Expression, ParseQueryError> {
+        let mut expression = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.index += 1;
+            expression = Expression::And(Box::new(expression), Box::new(self.unary()?));
+        }
+        Ok(expression)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ParseQueryError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.index += 1;
+                Ok(Expression::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.index += 1;
+                let expression = self.or()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(self.error("`)`"));
+                }
+                self.index += 1;
+                Ok(expression)
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expression, ParseQueryError> {
+        let operand = match self.peek() {
+            Some(Token::Identifier(name)) if name == "total" => Operand::Total,
+            Some(Token::Identifier(name)) => Operand::Colour(Colour::from(name.as_str())),
+            _ => return Err(self.error("a colour or `total`")),
+        };
+        self.index += 1;
+
+        let comparison = match self.peek() {
+            Some(Token::Comparison(comparison)) => *comparison,
+            _ => return Err(self.error("a comparison")),
+        };
+        self.index += 1;
+
+        let value = match self.peek() {
+            Some(Token::Number(value)) => *value,
+            _ => return Err(self.error("a number")),
+        };
+        self.index += 1;
+
+        Ok(Expression::Compare(operand, comparison, value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub expression: Expression,
+}
+
+impl FromStr for Query {
+    type Err = ParseQueryError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(query)?,
+            index: 0,
+            end: query.len(),
+        };
+        let expression = parser.or()?;
+        if parser.peek().is_some() {
+            return Err(parser.error("`&&`, `||` or the end of the query"));
+        }
+        Ok(Self { expression })
+    }
+}
+
+impl Query {
+    pub fn matches_reveal(&self, reveal: &Reveal) -> bool {
+        self.expression
+            .evaluate(&|colour| reveal.count(colour), reveal.total())
+    }
+
+    pub fn matches(&self, game: &Game, scope: Scope) -> bool {
+        match scope {
+            Scope::EveryReveal => game.reveals.iter().all(|r| self.matches_reveal(r)),
+            Scope::AnyReveal => game.reveals.iter().any(|r| self.matches_reveal(r)),
+            Scope::Game => {
+                let bag = game.minimum_bag();
+                self.expression
+                    .evaluate(&|colour| bag.count(colour), bag.total())
+            }
+        }
+    }
+
+    /// IDs of the matching games, in record order.
+    pub fn matching_games(&self, games: &[Game], scope: Scope) -> Vec<u32> {
+        games
+            .iter()
+            .filter(|game| self.matches(game, scope))
+            .map(|game| game.id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{consts::INPUT, game::parse_input};
+
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+    "#;
+
+    fn query(query: &str) -> Query {
+        query.parse().unwrap()
+    }
+
+    #[test]
+    fn it_works() {
+        let games = parse_input(EXAMPLE).unwrap();
+        let possible = query("red <= 12 && green <= 13 && blue <= 14");
+
+        assert_eq!(
+            possible.matching_games(&games, Scope::EveryReveal),
+            vec![1, 2, 5]
+        );
+        assert_eq!(
+            query("red <= 14 && green <= 13 && (blue <= 14 || total <= 32)")
+                .matching_games(&games, Scope::EveryReveal),
+            vec![1, 2, 4, 5]
+        );
+        assert_eq!(
+            query("red > 10").matching_games(&games, Scope::AnyReveal),
+            vec![3, 4]
+        );
+        assert_eq!(
+            query("total >= 30 || !(purple == 0)").matching_games(&games, Scope::Game),
+            vec![3, 4]
+        );
+    }
+
+    #[test]
+    fn it_works_for_puzzle() {
+        let games = parse_input(INPUT).unwrap();
+        let ids = query("red <= 12 && green <= 13 && blue <= 14")
+            .matching_games(&games, Scope::EveryReveal);
+
+        assert_eq!(ids.iter().sum::<u32>(), 2256);
+    }
+
+    #[test]
+    fn it_binds_and_tighter_than_or() {
+        assert_eq!(
+            query("red < 1 || red > 2 && blue != 3").expression,
+            Expression::Or(
+                Box::new(Expression::Compare(
+                    Operand::Colour(Colour::from("red")),
+                    Comparison::Less,
+                    1
+                )),
+                Box::new(Expression::And(
+                    Box::new(Expression::Compare(
+                        Operand::Colour(Colour::from("red")),
+                        Comparison::Greater,
+                        2
+                    )),
+                    Box::new(Expression::Compare(
+                        Operand::Colour(Colour::from("blue")),
+                        Comparison::NotEqual,
+                        3
+                    )),
+                ))
+            )
+        );
+    }
+
+    #[test]
+    fn it_reports_parse_errors() {
+        let error = |query: &str| query.parse::<Query>().unwrap_err();
+
+        assert_eq!(
+            error("red <= 12 &&"),
+            ParseQueryError {
+                position: 12,
+                expected: "a colour or `total`",
+            }
+        );
+        assert_eq!(error("(red < 1").expected, "`)`");
+        assert_eq!(error("red 12").expected, "a comparison");
+        assert_eq!(error("red <= blue").expected, "a number");
+        assert_eq!(error("red <= 1 blue").position, 9);
+        assert_eq!(
+            error("red = 1").to_string(),
+            "Expected a colour, a number or an operator at 4"
+        );
+        assert_eq!(error("12red < 1").position, 0);
+    }
+}
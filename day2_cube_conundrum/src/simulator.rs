@@ -0,0 +1,246 @@
+use utils::random::Random;
+
+use crate::{
+    game::{print_games, Bag, Colour, Game, Reveal},
+    minimum_bag::PowerPolicy,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationConfig {
+    /// The cubes hidden in the bag for every game, at least one.
+    pub bag: Bag,
+    pub games: usize,
+    /// Handfuls per game, picked uniformly between the two, at least one.
+    pub min_handfuls: usize,
+    pub max_handfuls: usize,
+}
+
+/// What the records of a game were generated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameTruth {
+    pub id: u32,
+    /// Most cubes of each colour of the bag shown at once, zero if never.
+    pub most_shown: Bag,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedGames {
+    pub input: String,
+    pub truths: Vec<GameTruth>,
+}
+
+impl SimulatedGames {
+    /// The part one answer for the given bag.
+    pub fn possible_id_sum(&self, bag: &Bag) -> u32 {
+        self.truths
+            .iter()
+            .filter(|truth| {
+                truth
+                    .most_shown
+                    .iter()
+                    .all(|(colour, &shown)| shown <= bag.get(colour).copied().unwrap_or(0))
+            })
+            .map(|truth| truth.id)
+            .sum()
+    }
+
+    /// The part two answer over every colour of the bag, whether or not a
+    /// game showed it.
+    pub fn power_sum(&self, policy: PowerPolicy) -> u64 {
+        self.truths
+            .iter()
+            .map(|truth| {
+                let mut power: Option<u64> = None;
+                for &shown in truth.most_shown.values() {
+                    if shown == 0 && policy == PowerPolicy::ObservedOnly {
+                        continue;
+                    }
+                    power = Some(power.unwrap_or(1) * shown as u64);
+                }
+                // a game showing no colour at all has no power either way
+                power.unwrap_or(0)
+            })
+            .sum()
+    }
+}
+
+/// Plays games the way the Elf does: every handful holds between one cube
+/// and the whole bag, uniformly, its cubes drawn without replacement and put
+/// back afterwards. Colours missing from a handful are left out of its
+/// record and listed colours are shuffled.
+pub fn simulate(seed: u64, config: &SimulationConfig) -> SimulatedGames {
+    let cubes = config
+        .bag
+        .iter()
+        .flat_map(|(colour, &count)| (0..count).map(move |_| colour.clone()))
+        .collect::<Vec<Colour>>();
+    assert!(!cubes.is_empty(), "Bag should hold at least one cube");
+    assert!(
+        0 < config.min_handfuls && config.min_handfuls <= config.max_handfuls,
+        "Games should have at least one handful"
+    );
+
+    let mut random = Random::new(seed);
+    let mut bag = cubes;
+    let mut games: Vec<Game> = vec![];
+    let mut truths: Vec<GameTruth> = vec![];
+
+    for id in 1..=config.games as u32 {
+        let mut most_shown = config
+            .bag
+            .keys()
+            .map(|colour| (colour.clone(), 0))
+            .collect::<Bag>();
+        let handfuls = random.between(config.min_handfuls as u64, config.max_handfuls as u64);
+
+        let reveals = (0..handfuls)
+            .map(|_| {
+                random.shuffle(&mut bag);
+                let size = random.between(1, bag.len() as u64) as usize;
+
+                let mut shown = Bag::new();
+                for colour in bag[..size].iter() {
+                    *shown.entry(colour.clone()).or_default() += 1;
+                }
+                for (colour, &count) in shown.iter() {
+                    let most = most_shown.get_mut(colour).unwrap();
+                    *most = (*most).max(count);
+                }
+
+                let mut cubes = shown.into_iter().collect::<Vec<(Colour, u32)>>();
+                random.shuffle(&mut cubes);
+                Reveal { cubes }
+            })
+            .collect::<Vec<Reveal>>();
+
+        games.push(Game { id, reveals });
+        truths.push(GameTruth { id, most_shown });
+    }
+
+    SimulatedGames {
+        input: print_games(&games, false),
+        truths,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{game::parse_input, part1, part2};
+
+    use super::*;
+
+    fn puzzle_bag() -> Bag {
+        Bag::from([
+            (Colour::from("red"), 12),
+            (Colour::from("green"), 13),
+            (Colour::from("blue"), 14),
+        ])
+    }
+
+    #[test]
+    fn it_works() {
+        let config = SimulationConfig {
+            bag: puzzle_bag(),
+            games: 20,
+            min_handfuls: 1,
+            max_handfuls: 6,
+        };
+        let simulated = simulate(1, &config);
+
+        assert_eq!(simulated, simulate(1, &config));
+        assert_ne!(simulated.input, simulate(2, &config).input);
+
+        let games = parse_input(&simulated.input).unwrap();
+        assert_eq!(games.len(), 20);
+        for (game, truth) in games.iter().zip(simulated.truths.iter()) {
+            assert_eq!(game.id, truth.id);
+            assert!((1..=6).contains(&game.reveals.len()));
+            assert!(game
+                .reveals
+                .iter()
+                .all(|reveal| (1..=39).contains(&reveal.total())));
+            assert!(game.is_possible(&config.bag));
+        }
+    }
+
+    #[test]
+    fn it_checks_part1_against_ground_truth() {
+        let config = SimulationConfig {
+            bag: puzzle_bag(),
+            games: 2_000,
+            min_handfuls: 1,
+            max_handfuls: 8,
+        };
+        let simulated = simulate(7, &config);
+        let mut random = Random::new(7);
+
+        assert_eq!(
+            part1::cube_conundrum(&simulated.input, &config.bag),
+            (1..=2_000).sum::<u32>()
+        );
+        for _ in 0..50 {
+            let bag = config
+                .bag
+                .iter()
+                .map(|(colour, &count)| (colour.clone(), random.between(0, count as u64) as u32))
+                .collect::<Bag>();
+            assert_eq!(
+                part1::cube_conundrum(&simulated.input, &bag),
+                simulated.possible_id_sum(&bag)
+            );
+        }
+    }
+
+    #[test]
+    fn it_computes_powers_from_the_bag_colours() {
+        let red = Colour::from("red");
+        let teal = Colour::from("teal");
+        let simulated = SimulatedGames {
+            input: String::new(),
+            truths: vec![
+                GameTruth {
+                    id: 1,
+                    most_shown: Bag::from([(red.clone(), 3), (teal.clone(), 2)]),
+                },
+                GameTruth {
+                    id: 2,
+                    most_shown: Bag::from([(red.clone(), 4), (teal.clone(), 0)]),
+                },
+                GameTruth {
+                    id: 3,
+                    most_shown: Bag::from([(red, 0), (teal, 0)]),
+                },
+            ],
+        };
+
+        assert_eq!(simulated.power_sum(PowerPolicy::Strict), 6);
+        assert_eq!(simulated.power_sum(PowerPolicy::ObservedOnly), 6 + 4);
+    }
+
+    #[test]
+    fn it_checks_part2_against_ground_truth() {
+        for seed in 0..20 {
+            let config = SimulationConfig {
+                bag: Bag::from([
+                    (Colour::from("red"), 1 + seed as u32 % 5),
+                    (Colour::from("purple"), 20),
+                    (Colour::from("teal"), seed as u32),
+                ]),
+                games: 500,
+                min_handfuls: 1,
+                max_handfuls: 3,
+            };
+            let simulated = simulate(seed, &config);
+            let colours = config.bag.keys().cloned().collect::<Vec<Colour>>();
+
+            assert_eq!(
+                part2::cube_conundrum_with(&simulated.input, &colours, PowerPolicy::ObservedOnly),
+                simulated.power_sum(PowerPolicy::ObservedOnly)
+            );
+            assert_eq!(
+                part2::cube_conundrum_with(&simulated.input, &colours, PowerPolicy::Strict),
+                simulated.power_sum(PowerPolicy::Strict)
+            );
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use crate::schematic::{PartNumber, Position, Schematic, Symbol};
+
+/// Inclusive corners of the smallest rectangle holding every tile of an
+/// assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub top_left: Position,
+    pub bottom_right: Position,
+}
+
+impl BoundingBox {
+    pub fn width(&self) -> usize {
+        self.bottom_right.0 - self.top_left.0 + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.bottom_right.1 - self.top_left.1 + 1
+    }
+}
+
+/// A connected group of numbers and symbols, members in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assembly {
+    pub part_numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+    pub total: u64,
+    pub bounding_box: BoundingBox,
+}
+
+// union-find over node ids, numbers first then symbols
+struct Components {
+    parents: Vec<usize>,
+}
+
+impl Components {
+    fn new(size: usize) -> Self {
+        Self {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, node: usize) -> usize {
+        let mut root = node;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut node = node;
+        while self.parents[node] != root {
+            let parent = self.parents[node];
+            self.parents[node] = root;
+            node = parent;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parents[a.max(b)] = a.min(b);
+        }
+    }
+}
+
+// the assembly of the given root, stretched to cover the given tiles
+fn grow<'a>(
+    assemblies: &'a mut HashMap<usize, Assembly>,
+    root: usize,
+    tiles: &[Position],
+) -> &'a mut Assembly {
+    let assembly = assemblies.entry(root).or_insert_with(|| Assembly {
+        part_numbers: vec![],
+        symbols: vec![],
+        total: 0,
+        bounding_box: BoundingBox {
+            top_left: tiles[0],
+            bottom_right: tiles[0],
+        },
+    });
+    let bounding_box = &mut assembly.bounding_box;
+    for &(x, y) in tiles {
+        bounding_box.top_left = (
+            bounding_box.top_left.0.min(x),
+            bounding_box.top_left.1.min(y),
+        );
+        bounding_box.bottom_right = (
+            bounding_box.bottom_right.0.max(x),
+            bounding_box.bottom_right.1.max(y),
+        );
+    }
+    assembly
+}
+
+/// Splits the schematic into assemblies: two items are connected when one
+/// lies in the neighbourhood of the other, whatever their kind. Numbers
+/// touching nothing form an assembly on their own.
+///
+/// Assemblies are sorted by their first tile in reading order.
+pub fn find_assemblies(schematic: &Schematic) -> Vec<Assembly> {
+    let part_numbers = schematic.part_numbers();
+    let symbols = schematic.symbols();
+
+    let part_number_ids = part_numbers
+        .iter()
+        .enumerate()
+        .map(|(id, n)| ((n.x_start, n.y), id))
+        .collect::<HashMap<Position, usize>>();
+    let symbol_ids = symbols
+        .iter()
+        .enumerate()
+        .map(|(id, s)| (s.position(), part_numbers.len() + id))
+        .collect::<HashMap<Position, usize>>();
+    let node_at = |position: Position| -> Option<usize> {
+        if let Some(part_number) = schematic.part_number_at(position) {
+            return Some(part_number_ids[&(part_number.x_start, part_number.y)]);
+        }
+        schematic
+            .symbol_at(position)
+            .map(|s| symbol_ids[&s.position()])
+    };
+
+    let mut components = Components::new(part_numbers.len() + symbols.len());
+    let neighbourhood = schematic.neighbourhood();
+    for (id, part_number) in part_numbers.iter().enumerate() {
+        for position in part_number.neighbourhood(neighbourhood) {
+            if let Some(other) = node_at(position) {
+                components.union(id, other);
+            }
+        }
+    }
+    for (id, symbol) in symbols.iter().enumerate() {
+        for position in symbol.neighbourhood(neighbourhood) {
+            if let Some(other) = node_at(position) {
+                components.union(part_numbers.len() + id, other);
+            }
+        }
+    }
+
+    let mut assemblies: HashMap<usize, Assembly> = HashMap::new();
+    for (id, part_number) in part_numbers.iter().enumerate() {
+        let assembly = grow(&mut assemblies, components.find(id), &part_number.tiles());
+        assembly.total += part_number.value as u64;
+        assembly.part_numbers.push(part_number.clone());
+    }
+    for (id, symbol) in symbols.iter().enumerate() {
+        let assembly = grow(
+            &mut assemblies,
+            components.find(part_numbers.len() + id),
+            &[symbol.position()],
+        );
+        assembly.symbols.push(*symbol);
+    }
+
+    let mut assemblies = assemblies.into_values().collect::<Vec<Assembly>>();
+    assemblies.sort_by_key(|assembly| {
+        let first_number = assembly.part_numbers.first().map(|n| (n.y, n.x_start));
+        let first_symbol = assembly.symbols.first().map(|s| (s.y, s.x));
+        match (first_number, first_symbol) {
+            (Some(a), Some(b)) => a.min(b),
+            (a, b) => a.or(b).unwrap(),
+        }
+    });
+    assemblies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let schematic = Schematic::parse(
+            r#"
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        "#,
+        );
+        let assemblies = find_assemblies(&schematic);
+
+        assert_eq!(
+            assemblies
+                .iter()
+                .map(|assembly| (
+                    assembly
+                        .part_numbers
+                        .iter()
+                        .map(|n| n.value)
+                        .collect::<Vec<u32>>(),
+                    assembly.symbols.iter().map(|s| s.value).collect::<String>(),
+                    assembly.total,
+                ))
+                .collect::<Vec<(Vec<u32>, String, u64)>>(),
+            vec![
+                (vec![467, 35], "*".to_string(), 502),
+                (vec![114], "".to_string(), 114),
+                (vec![633], "#".to_string(), 633),
+                (vec![617], "*".to_string(), 617),
+                (vec![592], "+".to_string(), 592),
+                (vec![58], "".to_string(), 58),
+                (vec![755, 598], "*".to_string(), 1353),
+                (vec![664], "$".to_string(), 664),
+            ]
+        );
+        assert_eq!(
+            assemblies[6].bounding_box,
+            BoundingBox {
+                top_left: (5, 7),
+                bottom_right: (8, 9),
+            }
+        );
+        assert_eq!(assemblies[0].bounding_box.width(), 4);
+        assert_eq!(assemblies[0].bounding_box.height(), 3);
+    }
+
+    #[test]
+    fn it_connects_items_of_the_same_kind() {
+        let schematic = Schematic::parse(
+            r#"
+            12.
+            ..#
+            .$.
+            ...
+            7..
+            .8.
+        "#,
+        );
+        let assemblies = find_assemblies(&schematic);
+
+        assert_eq!(assemblies.len(), 2);
+        assert_eq!(assemblies[0].symbols.len(), 2);
+        assert_eq!(assemblies[0].total, 12);
+        assert_eq!(assemblies[1].total, 15);
+        assert!(assemblies[1].symbols.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::schematic::Position;
+
+/// Which tiles around an item count as adjacent to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Up, down, left and right.
+    FourNeighbour,
+    /// The four neighbours plus the diagonals, as in the puzzle.
+    EightNeighbour,
+    /// Every tile at most `k` rows and `k` columns away.
+    Chebyshev(usize),
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> Vec<(i64, i64)> {
+        let radius = match self {
+            Self::FourNeighbour => return vec![(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Self::EightNeighbour => 1,
+            Self::Chebyshev(k) => *k as i64,
+        };
+        (-radius..=radius)
+            .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+            .filter(|&offset| offset != (0, 0))
+            .collect()
+    }
+
+    /// Every position around the given tiles, in reading order, without the
+    /// tiles themselves and clipped to the top and left edges.
+    pub fn around(&self, tiles: &[Position]) -> Vec<Position> {
+        let offsets = self.offsets();
+        let mut positions = tiles
+            .iter()
+            .flat_map(|&(x, y)| {
+                offsets.iter().filter_map(move |&(dx, dy)| {
+                    Some((
+                        x.checked_add_signed(dx as isize)?,
+                        y.checked_add_signed(dy as isize)?,
+                    ))
+                })
+            })
+            .filter(|position| !tiles.contains(position))
+            .collect::<Vec<Position>>();
+        positions.sort_by_key(|&(x, y)| (y, x));
+        positions.dedup();
+        positions
+    }
+}
+
+/// Which characters, besides digits, are symbols.
+#[derive(Debug, Clone)]
+pub enum SymbolClass {
+    Only(Vec<char>),
+    Predicate(fn(char) -> bool),
+    AllExcept(Vec<char>),
+}
+
+impl SymbolClass {
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_ascii_digit() {
+            return false;
+        }
+        match self {
+            Self::Only(symbols) => symbols.contains(&c),
+            Self::Predicate(predicate) => predicate(c),
+            Self::AllExcept(excluded) => !excluded.contains(&c),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SchematicConfig {
+    pub neighbourhood: Neighbourhood,
+    pub symbols: SymbolClass,
+}
+
+impl Default for SchematicConfig {
+    /// The puzzle rules: eight neighbours and anything but `.` is a symbol.
+    fn default() -> Self {
+        Self {
+            neighbourhood: Neighbourhood::EightNeighbour,
+            symbols: SymbolClass::AllExcept(vec!['.']),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_neighbourhoods() {
+        assert_eq!(Neighbourhood::FourNeighbour.around(&[(1, 1)]).len(), 4);
+        assert_eq!(
+            Neighbourhood::EightNeighbour.around(&[(1, 1)]),
+            Neighbourhood::Chebyshev(1).around(&[(1, 1)])
+        );
+        assert_eq!(Neighbourhood::Chebyshev(2).around(&[(2, 2)]).len(), 24);
+        assert_eq!(
+            Neighbourhood::FourNeighbour.around(&[(0, 0), (1, 0)]),
+            vec![(2, 0), (0, 1), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn it_classifies_symbols() {
+        let only = SymbolClass::Only(vec!['*', '#']);
+        let predicate = SymbolClass::Predicate(|c| c.is_ascii_punctuation() && c != '.');
+        let all_except = SymbolClass::AllExcept(vec!['.', '$']);
+
+        assert!(only.contains('*') && !only.contains('+'));
+        assert!(predicate.contains('+') && !predicate.contains('.') && !predicate.contains('a'));
+        assert!(all_except.contains('a') && !all_except.contains('$'));
+        assert!(!all_except.contains('7'));
+    }
+}
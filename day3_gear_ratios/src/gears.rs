@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::schematic::{PartNumber, Schematic, Symbol};
+
+/// Which symbols count as gears: a given symbol touching exactly a given
+/// number of part numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbol: char,
+    pub part_numbers: usize,
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        Self {
+            symbol: '*',
+            part_numbers: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gear {
+    pub symbol: Symbol,
+    pub part_numbers: Vec<PartNumber>,
+    /// Product of the part numbers, `None` when it does not fit in a `u64`.
+    pub ratio: Option<u64>,
+}
+
+/// Raised when a gear ratio, or the sum of the ratios up to that gear, no
+/// longer fits in a `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    pub symbol: Symbol,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Gear at ({}, {}) overflows a u64",
+            self.symbol.x, self.symbol.y
+        )
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+pub(crate) fn ratio(part_numbers: &[&PartNumber]) -> Option<u64> {
+    part_numbers
+        .iter()
+        .try_fold(1_u64, |ratio, n| ratio.checked_mul(n.value as u64))
+}
+
+/// Sum of the gear ratios, an error at the first gear that makes it overflow.
+pub fn sum_ratios<'a>(gears: impl IntoIterator<Item = &'a Gear>) -> Result<u64, OverflowError> {
+    gears.into_iter().try_fold(0_u64, |total, gear| {
+        gear.ratio
+            .and_then(|ratio| total.checked_add(ratio))
+            .ok_or(OverflowError {
+                symbol: gear.symbol,
+            })
+    })
+}
+
+/// Every gear of the schematic, in reading order of their symbol.
+pub fn find_gears(schematic: &Schematic, rule: &GearRule) -> Vec<Gear> {
+    schematic
+        .symbols()
+        .iter()
+        .filter(|symbol| symbol.value == rule.symbol)
+        .filter_map(|symbol| {
+            let part_numbers = schematic.part_numbers_touching(symbol);
+            if part_numbers.len() != rule.part_numbers {
+                return None;
+            }
+            Some(Gear {
+                symbol: *symbol,
+                ratio: ratio(&part_numbers),
+                part_numbers: part_numbers.into_iter().cloned().collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let schematic = Schematic::parse(
+            r#"
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        "#,
+        );
+        let gears = find_gears(&schematic, &GearRule::default());
+
+        assert_eq!(
+            gears
+                .iter()
+                .map(|gear| (
+                    gear.symbol.position(),
+                    gear.part_numbers
+                        .iter()
+                        .map(|n| n.value)
+                        .collect::<Vec<u32>>(),
+                    gear.ratio
+                ))
+                .collect::<Vec<((usize, usize), Vec<u32>, Option<u64>)>>(),
+            vec![
+                ((3, 1), vec![467, 35], Some(16345)),
+                ((5, 8), vec![755, 598], Some(451490)),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_requires_an_exact_number_of_part_numbers() {
+        let schematic = Schematic::parse(
+            r#"
+            1.2.3
+            .*.#.
+            4...5
+            ..*..
+            6....
+        "#,
+        );
+
+        assert_eq!(find_gears(&schematic, &GearRule::default()), vec![]);
+
+        let gears = find_gears(
+            &schematic,
+            &GearRule {
+                symbol: '*',
+                part_numbers: 3,
+            },
+        );
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].symbol.position(), (1, 1));
+        assert_eq!(gears[0].ratio, Some(8));
+
+        let gears = find_gears(
+            &schematic,
+            &GearRule {
+                symbol: '#',
+                part_numbers: 3,
+            },
+        );
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].ratio, Some(30));
+    }
+
+    #[test]
+    fn it_reports_overflowing_ratios() {
+        let schematic = Schematic::parse(
+            r#"
+            999999.999999
+            ......*......
+            999999.999999
+        "#,
+        );
+        let rule = GearRule {
+            symbol: '*',
+            part_numbers: 4,
+        };
+        let gears = find_gears(&schematic, &rule);
+        let symbol = schematic.symbols()[0];
+
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].ratio, None);
+        assert_eq!(sum_ratios(&gears), Err(OverflowError { symbol }));
+        assert_eq!(
+            OverflowError { symbol }.to_string(),
+            "Gear at (6, 1) overflows a u64"
+        );
+
+        let gears = find_gears(
+            &Schematic::parse("4294967295*4294967295"),
+            &GearRule::default(),
+        );
+        assert_eq!(gears[0].ratio, Some(u32::MAX as u64 * u32::MAX as u64));
+        assert!(sum_ratios(gears.iter().chain(gears.iter())).is_err());
+    }
+}
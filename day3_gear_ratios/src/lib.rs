@@ -1,3 +1,9 @@
+pub mod assemblies;
+pub mod config;
 pub mod consts;
+pub mod gears;
 pub mod part1;
 pub mod part2;
+pub mod render;
+pub mod schematic;
+pub mod streaming;
@@ -24,94 +24,21 @@
     Of course, the actual engine schematic is much larger. What is the sum of all of the part numbers in the engine schematic?
 */
 
-use utils::split_and_clean_input_into_lines;
+use crate::{config::SchematicConfig, schematic::Schematic};
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    split_and_clean_input_into_lines(input)
-        .iter()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>()
-}
+pub fn gear_ratios_with(input: &str, config: &SchematicConfig) -> u32 {
+    let schematic = Schematic::parse_with(input, config);
 
-#[derive(Debug)]
-struct PartNumber {
-    value: u32,
-    x_start: usize,
-    x_end: usize,
-    y: usize,
-}
-
-impl PartNumber {
-    pub fn is_adjacent(&self, x: usize, y: usize) -> bool {
-        let x_offset = x + 1;
-        let y_offset = y + 1;
-        x_offset >= self.x_start
-            && x_offset <= self.x_end + 2
-            && y_offset >= self.y
-            && y_offset <= self.y + 2
-    }
+    schematic
+        .part_numbers()
+        .iter()
+        .filter(|part_number| !schematic.symbols_touching(part_number).is_empty())
+        .map(|part_number| part_number.value)
+        .sum()
 }
 
 pub fn gear_ratios(input: &str) -> u32 {
-    let gears = parse_input(input);
-
-    let mut part_numbers: Vec<PartNumber> = Vec::new();
-    let mut symbols_positions: Vec<(usize, usize)> = Vec::new();
-
-    for i in 0..gears.len() {
-        let row = gears.get(i).unwrap();
-        let mut start_index = Option::None;
-        let mut current_gear_number = 0;
-
-        for j in 0..row.len() {
-            let cell = row.get(j).unwrap();
-            if cell.is_digit(10) {
-                if start_index.is_none() {
-                    start_index = Some(j);
-                }
-                let digit = cell.to_digit(10).unwrap();
-                current_gear_number = current_gear_number * 10 + digit;
-                continue;
-            }
-
-            if *cell != '.' {
-                symbols_positions.push((j, i));
-            }
-
-            if start_index.is_some() {
-                part_numbers.push(PartNumber {
-                    value: current_gear_number,
-                    x_start: start_index.unwrap(),
-                    x_end: j - 1,
-                    y: i,
-                });
-                start_index = Option::None;
-                current_gear_number = 0;
-            }
-        }
-
-        if start_index.is_some() {
-            part_numbers.push(PartNumber {
-                value: current_gear_number,
-                x_start: start_index.unwrap(),
-                x_end: row.len() - 1,
-                y: i,
-            });
-        }
-    }
-
-    let mut sum = 0;
-
-    for part_number in part_numbers {
-        for (x, y) in &symbols_positions {
-            if part_number.is_adjacent(*x, *y) {
-                sum += part_number.value;
-                break;
-            }
-        }
-    }
-
-    return sum;
+    gear_ratios_with(input, &SchematicConfig::default())
 }
 
 #[cfg(test)]
@@ -141,4 +68,26 @@ mod tests {
     fn it_works_with_puzzle() {
         assert_eq!(gear_ratios(INPUT), 532428);
     }
+
+    #[test]
+    fn it_works_with_large_schematics() {
+        let tile = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ];
+        let input = (0..40)
+            .flat_map(|_| tile.iter().map(|row| row.repeat(40)))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        assert_eq!(gear_ratios(&input), 4361 * 40 * 40);
+    }
 }
@@ -51,112 +51,19 @@
     What is the sum of all of the gear ratios in your engine schematic?
 */
 
-use std::collections::HashMap;
+use crate::{
+    gears::{find_gears, sum_ratios, GearRule, OverflowError},
+    schematic::Schematic,
+};
 
-use utils::split_and_clean_input_into_lines;
+pub fn checked_gear_ratios(input: &str) -> Result<u64, OverflowError> {
+    let schematic = Schematic::parse(input);
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    split_and_clean_input_into_lines(input)
-        .iter()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>()
+    sum_ratios(&find_gears(&schematic, &GearRule::default()))
 }
 
-#[derive(Debug)]
-struct PartNumber {
-    value: u32,
-    x_start: usize,
-    x_end: usize,
-    y: usize,
-}
-
-impl PartNumber {
-    pub fn is_adjacent(&self, x: &usize, y: &usize) -> bool {
-        let x_offset = x + 1;
-        let y_offset = y + 1;
-        x_offset >= self.x_start
-            && x_offset <= self.x_end + 2
-            && y_offset >= self.y
-            && y_offset <= self.y + 2
-    }
-}
-
-fn build_hash_key(x: &usize, y: &usize) -> usize {
-    x * 7793 + y * 7019
-}
-
-pub fn gear_ratios(input: &str) -> u32 {
-    let rows = parse_input(input);
-
-    let mut part_numbers: Vec<PartNumber> = Vec::new();
-    let mut symbols_positions: Vec<(usize, usize)> = Vec::new();
-
-    for i in 0..rows.len() {
-        let row = rows.get(i).unwrap();
-        let mut start_index = Option::None;
-        let mut current_gear_number = 0;
-
-        for j in 0..row.len() {
-            let cell = row.get(j).unwrap();
-            if cell.is_digit(10) {
-                if start_index.is_none() {
-                    start_index = Some(j);
-                }
-                let digit = cell.to_digit(10).unwrap();
-                current_gear_number = current_gear_number * 10 + digit;
-                continue;
-            }
-
-            if *cell == '*' {
-                symbols_positions.push((j, i));
-            }
-
-            if start_index.is_some() {
-                part_numbers.push(PartNumber {
-                    value: current_gear_number,
-                    x_start: start_index.unwrap(),
-                    x_end: j - 1,
-                    y: i,
-                });
-                start_index = Option::None;
-                current_gear_number = 0;
-            }
-        }
-
-        if start_index.is_some() {
-            part_numbers.push(PartNumber {
-                value: current_gear_number,
-                x_start: start_index.unwrap(),
-                x_end: row.len() - 1,
-                y: i,
-            });
-        }
-    }
-
-    let mut map: HashMap<usize, (u32, u32)> = HashMap::new();
-
-    for part_number in part_numbers {
-        for (x, y) in &symbols_positions {
-            if part_number.is_adjacent(x, y) {
-                let key = build_hash_key(x, y);
-                if map.contains_key(&key) {
-                    let (count, val) = map.get(&key).unwrap();
-                    map.insert(key, (count + 1, val * part_number.value));
-                } else {
-                    map.insert(key, (1, part_number.value));
-                }
-            }
-        }
-    }
-
-    let sum = map.iter().fold(0, |acc, (_, (count, value))| {
-        if *count > 1 {
-            return acc + value;
-        }
-        acc
-    });
-
-    return sum;
+pub fn gear_ratios(input: &str) -> u64 {
+    checked_gear_ratios(input).expect("Gear ratios should fit in a u64")
 }
 
 #[cfg(test)]
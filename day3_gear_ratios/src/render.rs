@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use utils::split_and_clean_input_into_lines;
+
+use crate::{
+    config::SchematicConfig,
+    gears::{find_gears, GearRule},
+    schematic::{Position, Schematic},
+};
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_PART: &str = "\x1b[1;32m";
+const ANSI_NON_PART: &str = "\x1b[2m";
+const ANSI_GEAR: &str = "\x1b[1;31m";
+
+const HTML_PART: &str = "font-weight:bold;color:#2e7d32";
+const HTML_NON_PART: &str = "opacity:0.4";
+const HTML_GEAR: &str = "font-weight:bold;color:#c62828";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Part numbers in `[]`, gears in `()`, everything else untouched.
+    Plain,
+    Ansi,
+    /// A `<pre>` block with inline styles, ready to paste in a page.
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Highlight {
+    Part,
+    NonPart,
+    Gear,
+}
+
+fn escape_html(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+fn paint(output: &mut String, text: &str, highlight: Option<Highlight>, mode: RenderMode) {
+    let text = match mode {
+        RenderMode::Html => escape_html(text),
+        _ => text.to_string(),
+    };
+    let highlight = match highlight {
+        Some(highlight) => highlight,
+        None => return output.push_str(&text),
+    };
+
+    match (mode, highlight) {
+        (RenderMode::Plain, Highlight::Part) => output.push_str(&format!("[{}]", text)),
+        (RenderMode::Plain, Highlight::Gear) => output.push_str(&format!("({})", text)),
+        (RenderMode::Plain, Highlight::NonPart) => output.push_str(&text),
+        (RenderMode::Ansi, _) => {
+            let colour = match highlight {
+                Highlight::Part => ANSI_PART,
+                Highlight::NonPart => ANSI_NON_PART,
+                Highlight::Gear => ANSI_GEAR,
+            };
+            output.push_str(&format!("{}{}{}", colour, text, ANSI_RESET));
+        }
+        (RenderMode::Html, _) => {
+            let style = match highlight {
+                Highlight::Part => HTML_PART,
+                Highlight::NonPart => HTML_NON_PART,
+                Highlight::Gear => HTML_GEAR,
+            };
+            output.push_str(&format!("<span style=\"{}\">{}</span>", style, text));
+        }
+    }
+}
+
+/// Re-emits the schematic with every number marked as a part number or not,
+/// and every symbol matching the gear rule marked as a gear.
+pub fn render_with(
+    input: &str,
+    config: &SchematicConfig,
+    gear_rule: &GearRule,
+    mode: RenderMode,
+) -> String {
+    let schematic = Schematic::parse_with(input, config);
+    let gears = find_gears(&schematic, gear_rule)
+        .iter()
+        .map(|gear| gear.symbol.position())
+        .collect::<HashSet<Position>>();
+
+    let mut output = String::new();
+    if mode == RenderMode::Html {
+        output.push_str("<pre>\n");
+    }
+
+    for (y, line) in split_and_clean_input_into_lines(input).iter().enumerate() {
+        let row = line.chars().collect::<Vec<char>>();
+        let mut x = 0;
+        while x < row.len() {
+            if let Some(part_number) = schematic.part_number_at((x, y)) {
+                let highlight = if schematic.symbols_touching(part_number).is_empty() {
+                    Highlight::NonPart
+                } else {
+                    Highlight::Part
+                };
+                let text = row[part_number.columns()].iter().collect::<String>();
+                paint(&mut output, &text, Some(highlight), mode);
+                x = part_number.x_end + 1;
+                continue;
+            }
+
+            let highlight = gears.contains(&(x, y)).then_some(Highlight::Gear);
+            paint(&mut output, &row[x].to_string(), highlight, mode);
+            x += 1;
+        }
+        output.push('\n');
+    }
+
+    if mode == RenderMode::Html {
+        output.push_str("</pre>\n");
+    }
+    output
+}
+
+pub fn render(input: &str, mode: RenderMode) -> String {
+    render_with(
+        input,
+        &SchematicConfig::default(),
+        &GearRule::default(),
+        mode,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        467..114..
+        ...*......
+        ..35..633.
+        ......#...
+        617*......
+        .....+.58.
+    "#;
+
+    #[test]
+    fn it_works() {
+        let expected = [
+            "[467]..114..",
+            "...(*)......",
+            "..[35]..[633].",
+            "......#...",
+            "[617]*......",
+            ".....+.58.",
+            "",
+        ];
+
+        assert_eq!(render(EXAMPLE, RenderMode::Plain), expected.join("\n"));
+    }
+
+    #[test]
+    fn it_colours_numbers_and_gears() {
+        let rendered = render("58.\n..*\n.4.", RenderMode::Ansi);
+
+        assert_eq!(
+            rendered,
+            "\x1b[1;32m58\x1b[0m.\n..\x1b[1;31m*\x1b[0m\n.\x1b[1;32m4\x1b[0m.\n"
+        );
+
+        let rendered = render("58.\n...\n<4>", RenderMode::Ansi);
+        assert_eq!(rendered, "\x1b[2m58\x1b[0m.\n...\n<\x1b[1;32m4\x1b[0m>\n");
+    }
+
+    #[test]
+    fn it_renders_html() {
+        let rendered = render("58.\n..*\n<4&", RenderMode::Html);
+
+        assert_eq!(
+            rendered,
+            [
+                "<pre>",
+                "<span style=\"font-weight:bold;color:#2e7d32\">58</span>.",
+                "..<span style=\"font-weight:bold;color:#c62828\">*</span>",
+                "&lt;<span style=\"font-weight:bold;color:#2e7d32\">4</span>&amp;",
+                "</pre>",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use std::ops::RangeInclusive;
+
+use utils::split_and_clean_input_into_lines;
+
+use crate::config::{Neighbourhood, SchematicConfig, SymbolClass};
+
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub value: u32,
+    pub x_start: usize,
+    pub x_end: usize,
+    pub y: usize,
+}
+
+impl PartNumber {
+    pub fn columns(&self) -> RangeInclusive<usize> {
+        self.x_start..=self.x_end
+    }
+
+    pub fn tiles(&self) -> Vec<Position> {
+        self.columns().map(|x| (x, self.y)).collect()
+    }
+
+    /// Every position around the number, in reading order, clipped to the
+    /// top and left edges. Positions past the right or bottom edge are left
+    /// for the caller to ignore.
+    pub fn neighbourhood(&self, neighbourhood: &Neighbourhood) -> Vec<Position> {
+        neighbourhood.around(&self.tiles())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub value: char,
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Symbol {
+    pub fn position(&self) -> Position {
+        (self.x, self.y)
+    }
+
+    pub fn neighbourhood(&self, neighbourhood: &Neighbourhood) -> Vec<Position> {
+        neighbourhood.around(&[self.position()])
+    }
+}
+
+/// The numbers and symbols of a single row, both in reading order.
+pub(crate) fn parse_row(
+    line: &str,
+    y: usize,
+    symbol_class: &SymbolClass,
+) -> (Vec<PartNumber>, Vec<Symbol>) {
+    let mut part_numbers: Vec<PartNumber> = Vec::new();
+    let mut symbols: Vec<Symbol> = Vec::new();
+    let mut start_index: Option<usize> = None;
+    let mut current_gear_number = 0;
+    let mut width = 0;
+
+    for (x, cell) in line.chars().enumerate() {
+        width = x + 1;
+        if let Some(digit) = cell.to_digit(10) {
+            start_index.get_or_insert(x);
+            current_gear_number = current_gear_number * 10 + digit;
+            continue;
+        }
+
+        if symbol_class.contains(cell) {
+            symbols.push(Symbol { value: cell, x, y });
+        }
+
+        if let Some(x_start) = start_index.take() {
+            part_numbers.push(PartNumber {
+                value: current_gear_number,
+                x_start,
+                x_end: x - 1,
+                y,
+            });
+            current_gear_number = 0;
+        }
+    }
+
+    if let Some(x_start) = start_index {
+        part_numbers.push(PartNumber {
+            value: current_gear_number,
+            x_start,
+            x_end: width - 1,
+            y,
+        });
+    }
+    (part_numbers, symbols)
+}
+
+/// An engine schematic: its part numbers and symbols, both indexed by
+/// position so that adjacency queries only look at the tiles around an item.
+#[derive(Debug)]
+pub struct Schematic {
+    part_numbers: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+    // index into part_numbers for every digit, index[y][x]
+    part_number_index: Vec<Vec<Option<usize>>>,
+    // index into symbols, index[y][x]
+    symbol_index: Vec<Vec<Option<usize>>>,
+    neighbourhood: Neighbourhood,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with(input, &SchematicConfig::default())
+    }
+
+    pub fn parse_with(input: &str, config: &SchematicConfig) -> Self {
+        let mut part_numbers: Vec<PartNumber> = Vec::new();
+        let mut symbols: Vec<Symbol> = Vec::new();
+        let mut part_number_index: Vec<Vec<Option<usize>>> = Vec::new();
+        let mut symbol_index: Vec<Vec<Option<usize>>> = Vec::new();
+
+        for (y, line) in split_and_clean_input_into_lines(input).iter().enumerate() {
+            let width = line.chars().count();
+            let mut part_number_row = vec![None; width];
+            let mut symbol_row = vec![None; width];
+            let (row_part_numbers, row_symbols) = parse_row(line, y, &config.symbols);
+
+            for part_number in row_part_numbers {
+                for x in part_number.columns() {
+                    part_number_row[x] = Some(part_numbers.len());
+                }
+                part_numbers.push(part_number);
+            }
+            for symbol in row_symbols {
+                symbol_row[symbol.x] = Some(symbols.len());
+                symbols.push(symbol);
+            }
+            part_number_index.push(part_number_row);
+            symbol_index.push(symbol_row);
+        }
+
+        Self {
+            part_numbers,
+            symbols,
+            part_number_index,
+            symbol_index,
+            neighbourhood: config.neighbourhood,
+        }
+    }
+
+    /// Every number of the schematic, in reading order, whether or not it
+    /// touches a symbol.
+    pub fn part_numbers(&self) -> &[PartNumber] {
+        &self.part_numbers
+    }
+
+    /// Every symbol of the schematic, in reading order.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn symbol_at(&self, (x, y): Position) -> Option<&Symbol> {
+        let index = (*self.symbol_index.get(y)?.get(x)?)?;
+        Some(&self.symbols[index])
+    }
+
+    pub fn part_number_at(&self, (x, y): Position) -> Option<&PartNumber> {
+        let index = (*self.part_number_index.get(y)?.get(x)?)?;
+        Some(&self.part_numbers[index])
+    }
+
+    pub fn neighbourhood(&self) -> &Neighbourhood {
+        &self.neighbourhood
+    }
+
+    pub fn symbols_touching(&self, part_number: &PartNumber) -> Vec<&Symbol> {
+        part_number
+            .neighbourhood(&self.neighbourhood)
+            .into_iter()
+            .filter_map(|position| self.symbol_at(position))
+            .collect()
+    }
+
+    /// Numbers with a digit next to the symbol, each reported once.
+    pub fn part_numbers_touching(&self, symbol: &Symbol) -> Vec<&PartNumber> {
+        let mut indexes = symbol
+            .neighbourhood(&self.neighbourhood)
+            .into_iter()
+            .filter_map(|(x, y)| *self.part_number_index.get(y)?.get(x)?)
+            .collect::<Vec<usize>>();
+        indexes.sort();
+        indexes.dedup();
+        indexes
+            .into_iter()
+            .map(|index| &self.part_numbers[index])
+            .collect()
+    }
+
+    pub fn part_numbers_touching_no_symbol(&self) -> Vec<&PartNumber> {
+        self.part_numbers
+            .iter()
+            .filter(|part_number| self.symbols_touching(part_number).is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        467..114..
+        ...*......
+        ..35..633.
+        ......#...
+        617*......
+        .....+.58.
+        ..592.....
+        ......755.
+        ...$.*....
+        .664.598..
+    "#;
+
+    #[test]
+    fn it_looks_around_numbers() {
+        let part_number = PartNumber {
+            value: 467,
+            x_start: 0,
+            x_end: 2,
+            y: 0,
+        };
+
+        assert_eq!(
+            part_number.neighbourhood(&Neighbourhood::EightNeighbour),
+            vec![(3, 0), (0, 1), (1, 1), (2, 1), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn it_works() {
+        let schematic = Schematic::parse(EXAMPLE);
+
+        assert_eq!(schematic.part_numbers().len(), 10);
+        assert_eq!(schematic.symbols().len(), 6);
+        assert_eq!(
+            schematic.symbol_at((3, 1)),
+            Some(&Symbol {
+                value: '*',
+                x: 3,
+                y: 1
+            })
+        );
+        assert_eq!(schematic.symbol_at((4, 1)), None);
+        assert_eq!(schematic.symbol_at((40, 10)), None);
+        assert_eq!(
+            schematic
+                .part_number_at((7, 2))
+                .map(|n| (n.value, n.columns())),
+            Some((633, 6..=8))
+        );
+    }
+
+    #[test]
+    fn it_answers_adjacency_queries() {
+        let schematic = Schematic::parse(EXAMPLE);
+
+        let star = schematic.symbol_at((3, 1)).unwrap();
+        assert_eq!(
+            schematic
+                .part_numbers_touching(star)
+                .iter()
+                .map(|n| n.value)
+                .collect::<Vec<u32>>(),
+            vec![467, 35]
+        );
+
+        let number = schematic.part_number_at((0, 4)).unwrap();
+        assert_eq!(
+            schematic
+                .symbols_touching(number)
+                .iter()
+                .map(|s| (s.value, s.position()))
+                .collect::<Vec<(char, Position)>>(),
+            vec![('*', (3, 4))]
+        );
+
+        assert_eq!(
+            schematic
+                .part_numbers_touching_no_symbol()
+                .iter()
+                .map(|n| n.value)
+                .collect::<Vec<u32>>(),
+            vec![114, 58]
+        );
+    }
+
+    #[test]
+    fn it_can_be_configured() {
+        let config = SchematicConfig {
+            neighbourhood: Neighbourhood::FourNeighbour,
+            symbols: SymbolClass::Only(vec!['*']),
+        };
+        let schematic = Schematic::parse_with(EXAMPLE, &config);
+
+        assert_eq!(schematic.symbols().len(), 3);
+        assert_eq!(
+            schematic
+                .part_numbers_touching_no_symbol()
+                .iter()
+                .map(|n| n.value)
+                .collect::<Vec<u32>>(),
+            vec![467, 114, 633, 58, 592, 755, 664]
+        );
+
+        let config = SchematicConfig {
+            neighbourhood: Neighbourhood::Chebyshev(2),
+            ..SchematicConfig::default()
+        };
+        let schematic = Schematic::parse_with(EXAMPLE, &config);
+
+        assert!(schematic.part_numbers_touching_no_symbol().is_empty());
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
+
+use crate::{
+    config::SchematicConfig,
+    gears::{ratio, Gear, GearRule, OverflowError},
+    schematic::{parse_row, PartNumber, Symbol},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A number touching at least one symbol.
+    PartNumber(PartNumber),
+    Gear(Gear),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub part_numbers: u64,
+    pub gear_ratios: u64,
+}
+
+// the numbers and symbols of a single row, both sorted by column
+#[derive(Debug)]
+struct Row {
+    part_numbers: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+}
+
+impl Row {
+    fn symbol_at(&self, x: usize) -> Option<&Symbol> {
+        let index = self.symbols.binary_search_by_key(&x, |s| s.x).ok()?;
+        Some(&self.symbols[index])
+    }
+
+    fn part_number_at(&self, x: usize) -> Option<&PartNumber> {
+        let index = self.part_numbers.partition_point(|n| n.x_end < x);
+        self.part_numbers
+            .get(index)
+            .filter(|part_number| part_number.columns().contains(&x))
+    }
+}
+
+/// Solves a schematic row by row, only ever holding three rows in memory.
+/// A row is settled once the row below it is read: its part numbers and
+/// gears are then emitted, in reading order.
+///
+/// Only neighbourhoods reaching one row up and down are supported.
+pub struct StreamingSolver<R> {
+    reader: R,
+    line: String,
+    y: usize,
+    offsets: Vec<(i64, i64)>,
+    config: SchematicConfig,
+    gear_rule: GearRule,
+    previous: Option<Row>,
+    current: Option<Row>,
+    pending: VecDeque<Event>,
+    done: bool,
+}
+
+impl<R: BufRead> StreamingSolver<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_config(reader, SchematicConfig::default(), GearRule::default())
+    }
+
+    pub fn with_config(reader: R, config: SchematicConfig, gear_rule: GearRule) -> Self {
+        let offsets = config.neighbourhood.offsets();
+        assert!(
+            offsets.iter().all(|&(_, dy)| (-1..=1).contains(&dy)),
+            "Neighbourhood should not reach further than the next row"
+        );
+
+        Self {
+            reader,
+            line: String::new(),
+            y: 0,
+            offsets,
+            config,
+            gear_rule,
+            previous: None,
+            current: None,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Consumes the whole reader, summing part numbers and gear ratios. A
+    /// gear ratio sum overflowing a `u64` is reported as invalid data
+    /// wrapping an `OverflowError`.
+    pub fn totals(self) -> io::Result<Totals> {
+        let mut totals = Totals::default();
+        for event in self {
+            match event? {
+                Event::PartNumber(part_number) => totals.part_numbers += part_number.value as u64,
+                Event::Gear(gear) => {
+                    totals.gear_ratios = gear
+                        .ratio
+                        .and_then(|ratio| totals.gear_ratios.checked_add(ratio))
+                        .ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                OverflowError {
+                                    symbol: gear.symbol,
+                                },
+                            )
+                        })?;
+                }
+            }
+        }
+        Ok(totals)
+    }
+
+    // like `Schematic::parse`, blank lines before the first row are skipped
+    // and later ones are empty rows, the spaces around every line ignored
+    fn read_row(&mut self) -> io::Result<Option<Row>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            let line = self.line.trim();
+            if line.is_empty() && self.y == 0 {
+                continue;
+            }
+
+            let (part_numbers, symbols) = parse_row(line, self.y, &self.config.symbols);
+            self.y += 1;
+            return Ok(Some(Row {
+                part_numbers,
+                symbols,
+            }));
+        }
+    }
+
+    fn settle(&mut self, current: &Row, next: Option<&Row>) {
+        let window = [self.previous.as_ref(), Some(current), next];
+        let row_at = |dy: i64| window[(dy + 1) as usize];
+
+        for part_number in current.part_numbers.iter() {
+            let touches_symbol = part_number.columns().any(|x| {
+                self.offsets.iter().any(|&(dx, dy)| {
+                    let x = match x.checked_add_signed(dx as isize) {
+                        Some(x) => x,
+                        None => return false,
+                    };
+                    row_at(dy).and_then(|row| row.symbol_at(x)).is_some()
+                })
+            });
+            if touches_symbol {
+                self.pending
+                    .push_back(Event::PartNumber(part_number.clone()));
+            }
+        }
+
+        for symbol in current
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.value == self.gear_rule.symbol)
+        {
+            let mut part_numbers = self
+                .offsets
+                .iter()
+                .filter_map(|&(dx, dy)| {
+                    let x = symbol.x.checked_add_signed(dx as isize)?;
+                    row_at(dy)?.part_number_at(x)
+                })
+                .collect::<Vec<&PartNumber>>();
+            part_numbers.sort_by_key(|n| (n.y, n.x_start));
+            part_numbers.dedup();
+
+            if part_numbers.len() == self.gear_rule.part_numbers {
+                self.pending.push_back(Event::Gear(Gear {
+                    symbol: *symbol,
+                    ratio: ratio(&part_numbers),
+                    part_numbers: part_numbers.into_iter().cloned().collect(),
+                }));
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for StreamingSolver<R> {
+    type Item = io::Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+
+            let next = match self.read_row() {
+                Ok(next) => next,
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            };
+            self.done = next.is_none();
+
+            if let Some(current) = self.current.take() {
+                self.settle(&current, next.as_ref());
+                self.previous = Some(current);
+            }
+            self.current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{config::Neighbourhood, consts::INPUT, part1, part2};
+
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        467..114..
+        ...*......
+        ..35..633.
+        ......#...
+        617*......
+        .....+.58.
+        ..592.....
+        ......755.
+        ...$.*....
+        .664.598..
+    "#;
+
+    #[test]
+    fn it_works() {
+        let events = StreamingSolver::new(EXAMPLE.as_bytes())
+            .collect::<io::Result<Vec<Event>>>()
+            .unwrap();
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| match event {
+                    Event::PartNumber(part_number) => (part_number.value as u64, 'n'),
+                    Event::Gear(gear) => (gear.ratio.unwrap(), 'g'),
+                })
+                .collect::<Vec<(u64, char)>>(),
+            vec![
+                (467, 'n'),
+                (16345, 'g'),
+                (35, 'n'),
+                (633, 'n'),
+                (617, 'n'),
+                (592, 'n'),
+                (755, 'n'),
+                (451490, 'g'),
+                (664, 'n'),
+                (598, 'n'),
+            ]
+        );
+        assert_eq!(
+            StreamingSolver::new(EXAMPLE.as_bytes()).totals().unwrap(),
+            Totals {
+                part_numbers: 4361,
+                gear_ratios: 467835,
+            }
+        );
+    }
+
+    #[test]
+    fn it_works_with_puzzle() {
+        assert_eq!(
+            StreamingSolver::new(INPUT.as_bytes()).totals().unwrap(),
+            Totals {
+                part_numbers: part1::gear_ratios(INPUT) as u64,
+                gear_ratios: part2::gear_ratios(INPUT),
+            }
+        );
+    }
+
+    #[test]
+    fn it_can_be_configured() {
+        let config = SchematicConfig {
+            neighbourhood: Neighbourhood::FourNeighbour,
+            ..SchematicConfig::default()
+        };
+
+        assert_eq!(
+            StreamingSolver::with_config(INPUT.as_bytes(), config.clone(), GearRule::default())
+                .totals()
+                .unwrap()
+                .part_numbers,
+            part1::gear_ratios_with(INPUT, &config) as u64
+        );
+    }
+
+    #[test]
+    fn it_keeps_blank_lines_as_rows() {
+        for input in ["1\n\n*", "\n\n.1.\n\n\n.*.\n\n", "12\n\n*..\n.34\n\n#"] {
+            let streamed = StreamingSolver::new(input.as_bytes()).totals().unwrap();
+
+            assert_eq!(streamed.part_numbers, part1::gear_ratios(input) as u64);
+            assert_eq!(streamed.gear_ratios, part2::gear_ratios(input));
+        }
+        assert_eq!(
+            StreamingSolver::new("1\n\n*".as_bytes())
+                .totals()
+                .unwrap()
+                .part_numbers,
+            0
+        );
+    }
+
+    #[test]
+    fn it_reports_overflowing_ratios() {
+        let gear_rule = GearRule {
+            symbol: '*',
+            part_numbers: 4,
+        };
+        let input = "999999.999999\n......*......\n999999.999999";
+        let error =
+            StreamingSolver::with_config(input.as_bytes(), SchematicConfig::default(), gear_rule)
+                .totals()
+                .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "Gear at (6, 1) overflows a u64");
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_rejects_wide_neighbourhoods() {
+        let config = SchematicConfig {
+            neighbourhood: Neighbourhood::Chebyshev(2),
+            ..SchematicConfig::default()
+        };
+        StreamingSolver::with_config(EXAMPLE.as_bytes(), config, GearRule::default());
+    }
+}
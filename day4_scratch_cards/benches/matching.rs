@@ -0,0 +1,114 @@
+//! Compares card matching with `Vec::contains` against the bitset card model.
+//! Run with `cargo bench -p day4_scratch_cards`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use day4_scratch_cards::{
+    card::{parse_input, Card, NumberSet},
+    consts::INPUT,
+};
+
+const ITERATIONS: u32 = 2_000;
+// moves every number past the bitset range
+const SHIFT: u32 = 1_000;
+
+fn get_numbers_from_str(input: &str) -> Vec<u32> {
+    input
+        .split_whitespace()
+        .map(|val| val.parse::<u32>().unwrap())
+        .collect::<Vec<u32>>()
+}
+
+// the previous approach: numbers kept as vectors, one scan per winning number
+fn parse_vectors(input: &str) -> Vec<(Vec<u32>, Vec<u32>)> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let (winning_numbers, selected_numbers) =
+                line.split_once(':').unwrap().1.split_once('|').unwrap();
+            (
+                get_numbers_from_str(winning_numbers),
+                get_numbers_from_str(selected_numbers),
+            )
+        })
+        .collect()
+}
+
+fn vector_matches(cards: &[(Vec<u32>, Vec<u32>)]) -> usize {
+    cards
+        .iter()
+        .map(|(winning_numbers, selected_numbers)| {
+            winning_numbers
+                .iter()
+                .filter(|val| selected_numbers.contains(val))
+                .count()
+        })
+        .sum()
+}
+
+fn card_matches(cards: &[Card]) -> usize {
+    cards.iter().map(|card| card.match_count()).sum()
+}
+
+fn time(name: &str, mut run: impl FnMut() -> usize) -> Duration {
+    let expected = run();
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        assert_eq!(black_box(run()), expected);
+    }
+    let elapsed = started.elapsed();
+    println!(
+        "{:<24} {:>10.2?} per run ({} matches)",
+        name,
+        elapsed / ITERATIONS,
+        expected
+    );
+    elapsed
+}
+
+fn main() {
+    let vectors = parse_vectors(INPUT);
+    let cards = parse_input(INPUT);
+    // same numbers shifted past the bitset range, to exercise the fallback
+    let hashed = cards
+        .iter()
+        .map(|card| Card {
+            id: card.id,
+            winning_numbers: shifted(&card.winning_numbers),
+            repeated_winning_numbers: card
+                .repeated_winning_numbers
+                .iter()
+                .map(|number| number + SHIFT)
+                .collect(),
+            selected_numbers: shifted(&card.selected_numbers),
+        })
+        .collect::<Vec<Card>>();
+    assert_eq!(card_matches(&hashed), card_matches(&cards));
+
+    let vector = time("vec contains", || vector_matches(black_box(&vectors)));
+    let bitset = time("bitset popcount", || card_matches(black_box(&cards)));
+    time("hash set fallback", || card_matches(black_box(&hashed)));
+    time("parse + vec contains", || {
+        vector_matches(&parse_vectors(black_box(INPUT)))
+    });
+    time("parse + bitset", || {
+        card_matches(&parse_input(black_box(INPUT)))
+    });
+
+    println!(
+        "bitset matching is {:.1}x faster",
+        vector.as_secs_f64() / bitset.as_secs_f64()
+    );
+}
+
+fn shifted(numbers: &NumberSet) -> NumberSet {
+    let numbers = (0..128)
+        .filter(|&number| numbers.contains(number))
+        .map(|number| number + SHIFT)
+        .collect::<Vec<u32>>();
+    NumberSet::from_numbers(&numbers)
+}
@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+
+use utils::split_and_clean_input_into_lines;
+
+// numbers below this fit in a bitset, puzzle numbers stay under 100
+const BITSET_BITS: u32 = 128;
+const WORD_BITS: u32 = u64::BITS;
+
+/// A set of card numbers, stored as a fixed bitset while every number is
+/// small and as a hash set otherwise. Sets holding the same numbers are
+/// equal whatever their storage.
+#[derive(Debug, Clone)]
+pub enum NumberSet {
+    Bits([u64; (BITSET_BITS / WORD_BITS) as usize]),
+    Hashed(HashSet<u32>),
+}
+
+impl NumberSet {
+    pub fn from_numbers(numbers: &[u32]) -> Self {
+        if numbers.iter().any(|&number| number >= BITSET_BITS) {
+            return Self::Hashed(numbers.iter().copied().collect());
+        }
+
+        let mut words = [0; (BITSET_BITS / WORD_BITS) as usize];
+        for &number in numbers {
+            words[(number / WORD_BITS) as usize] |= 1 << (number % WORD_BITS);
+        }
+        Self::Bits(words)
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        match self {
+            Self::Bits(words) => {
+                number < BITSET_BITS
+                    && words[(number / WORD_BITS) as usize] & (1 << (number % WORD_BITS)) != 0
+            }
+            Self::Hashed(numbers) => numbers.contains(&number),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Bits(words) => words.iter().map(|word| word.count_ones() as usize).sum(),
+            Self::Hashed(numbers) => numbers.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of values held by both sets, a popcount when both are bitsets.
+    pub fn count_common(&self, other: &Self) -> usize {
+        match (self, other) {
+            (Self::Bits(a), Self::Bits(b)) => a
+                .iter()
+                .zip(b.iter())
+                .map(|(a, b)| (a & b).count_ones() as usize)
+                .sum(),
+            (Self::Hashed(numbers), other) | (other, Self::Hashed(numbers)) => numbers
+                .iter()
+                .filter(|&&number| other.contains(number))
+                .count(),
+        }
+    }
+}
+
+impl PartialEq for NumberSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.count_common(other) == self.len()
+    }
+}
+
+impl Eq for NumberSet {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning_numbers: NumberSet,
+    /// Winning numbers listed again after their first occurrence, once per
+    /// repetition. They keep matching as many times as they are listed.
+    pub repeated_winning_numbers: Vec<u32>,
+    pub selected_numbers: NumberSet,
+}
+
+fn get_numbers_from_str(input: &str) -> Vec<u32> {
+    input
+        .split_whitespace()
+        .map(|val| val.parse::<u32>().unwrap())
+        .collect::<Vec<u32>>()
+}
+
+impl Card {
+    pub fn parse(input: &str) -> Self {
+        let (card_metadata, card_data) = input.split_once(':').unwrap();
+        let id = card_metadata
+            .split_whitespace()
+            .last()
+            .unwrap()
+            .parse::<u32>()
+            .unwrap();
+        let (winning_numbers_str, selected_numbers_str) = card_data.split_once('|').unwrap();
+        let winning_numbers = get_numbers_from_str(winning_numbers_str);
+        let repeated_winning_numbers = winning_numbers
+            .iter()
+            .enumerate()
+            .filter(|&(i, number)| winning_numbers[..i].contains(number))
+            .map(|(_, &number)| number)
+            .collect::<Vec<u32>>();
+
+        Self {
+            id,
+            winning_numbers: NumberSet::from_numbers(&winning_numbers),
+            repeated_winning_numbers,
+            selected_numbers: NumberSet::from_numbers(&get_numbers_from_str(selected_numbers_str)),
+        }
+    }
+
+    /// Number of listed winning numbers that were selected, a winning number
+    /// listed twice matching twice.
+    pub fn match_count(&self) -> usize {
+        self.winning_numbers.count_common(&self.selected_numbers)
+            + self
+                .repeated_winning_numbers
+                .iter()
+                .filter(|&&number| self.selected_numbers.contains(number))
+                .count()
+    }
+}
+
+pub fn parse_input(input: &str) -> Vec<Card> {
+    split_and_clean_input_into_lines(input)
+        .iter()
+        .map(|card| Card::parse(card))
+        .collect::<Vec<Card>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let card = Card::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+
+        assert_eq!(card.id, 1);
+        assert!(matches!(card.winning_numbers, NumberSet::Bits(_)));
+        assert_eq!(card.winning_numbers.len(), 5);
+        assert_eq!(card.selected_numbers.len(), 8);
+        assert_eq!(card.match_count(), 4);
+    }
+
+    #[test]
+    fn it_falls_back_to_hash_sets_for_large_numbers() {
+        let card = Card::parse("Card 12: 41 1000 83 127 | 83 127 1000 5000 6");
+
+        assert_eq!(card.id, 12);
+        assert!(matches!(card.winning_numbers, NumberSet::Hashed(_)));
+        assert!(matches!(card.selected_numbers, NumberSet::Hashed(_)));
+        assert_eq!(card.match_count(), 3);
+
+        let small = NumberSet::from_numbers(&[0, 63, 64, 127]);
+        let large = NumberSet::from_numbers(&[63, 64, 128, 4096]);
+        assert!(small.contains(127) && !small.contains(128));
+        assert_eq!(small.count_common(&large), 2);
+        assert_eq!(large.count_common(&small), 2);
+    }
+
+    #[test]
+    fn it_counts_repeated_winning_numbers_every_time() {
+        let card = Card::parse("Card 3: 5 7 5 5 | 5 9 5");
+
+        assert_eq!(card.winning_numbers.len(), 2);
+        assert_eq!(card.repeated_winning_numbers, vec![5, 5]);
+        assert_eq!(card.match_count(), 3);
+        assert_eq!(Card::parse("Card 4: 7 7 | 5").match_count(), 0);
+    }
+
+    #[test]
+    fn it_compares_sets_by_content() {
+        let bits = NumberSet::from_numbers(&[3, 64, 100]);
+        let mut hashed = NumberSet::Hashed([100, 3, 64].into_iter().collect());
+
+        assert!(matches!(bits, NumberSet::Bits(_)));
+        assert_eq!(bits, hashed);
+        assert_eq!(hashed, bits);
+        if let NumberSet::Hashed(numbers) = &mut hashed {
+            numbers.insert(4);
+        }
+        assert_ne!(bits, hashed);
+        assert_ne!(bits, NumberSet::from_numbers(&[3, 64, 101]));
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::card::Card;
+
+/// Raised when a count no longer fits in a `u64`, with the ID of the card
+/// being processed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    pub card: u32,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Card {} overflows a u64", self.card)
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+/// Copies of a card won from an earlier card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contribution {
+    pub from: u32,
+    pub copies: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardReport {
+    pub id: u32,
+    pub match_count: usize,
+    /// Part one points: 1 for the first match, doubled for every other.
+    /// `None` when they do not fit in a `u64`, which never stops the copies.
+    pub points: Option<u64>,
+    /// The original plus every copy won.
+    pub instances: u64,
+    /// Earlier cards that won copies of this one, in table order.
+    pub contributions: Vec<Contribution>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CascadeReport {
+    pub cards: Vec<CardReport>,
+    pub total: u64,
+}
+
+fn points(match_count: usize) -> Option<u64> {
+    if match_count == 0 {
+        return Some(0);
+    }
+    u32::try_from(match_count - 1)
+        .ok()
+        .and_then(|shift| 1_u64.checked_shl(shift))
+}
+
+/// Plays the copy rules of part two over the table. A card wins copies of
+/// the cards following it in the table, never past its end, so IDs are only
+/// used for reporting.
+pub fn cascade(cards: &[Card]) -> Result<CascadeReport, OverflowError> {
+    let mut reports = cards
+        .iter()
+        .map(|card| {
+            let match_count = card.match_count();
+            CardReport {
+                id: card.id,
+                match_count,
+                points: points(match_count),
+                instances: 1,
+                contributions: vec![],
+            }
+        })
+        .collect::<Vec<CardReport>>();
+
+    let mut total: u64 = 0;
+    for i in 0..reports.len() {
+        let (id, instances) = (reports[i].id, reports[i].instances);
+        total = total
+            .checked_add(instances)
+            .ok_or(OverflowError { card: id })?;
+
+        let last = (i + reports[i].match_count).min(reports.len() - 1);
+        for report in reports[i + 1..=last].iter_mut() {
+            report.instances = report
+                .instances
+                .checked_add(instances)
+                .ok_or(OverflowError { card: report.id })?;
+            report.contributions.push(Contribution {
+                from: id,
+                copies: instances,
+            });
+        }
+    }
+
+    Ok(CascadeReport {
+        cards: reports,
+        total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::card::parse_input;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let cards = parse_input(
+            r#"
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        "#,
+        );
+        let report = cascade(&cards).unwrap();
+
+        assert_eq!(report.total, 30);
+        assert_eq!(
+            report
+                .cards
+                .iter()
+                .map(|card| (card.id, card.match_count, card.points, card.instances))
+                .collect::<Vec<(u32, usize, Option<u64>, u64)>>(),
+            vec![
+                (1, 4, Some(8), 1),
+                (2, 2, Some(2), 2),
+                (3, 2, Some(2), 4),
+                (4, 1, Some(1), 8),
+                (5, 0, Some(0), 14),
+                (6, 0, Some(0), 1),
+            ]
+        );
+        assert_eq!(
+            report.cards[4].contributions,
+            vec![
+                Contribution { from: 1, copies: 1 },
+                Contribution { from: 3, copies: 4 },
+                Contribution { from: 4, copies: 8 },
+            ]
+        );
+        assert!(report.cards[0].contributions.is_empty());
+    }
+
+    #[test]
+    fn it_reports_overflows() {
+        // every card wins a copy of all the following ones, doubling instances
+        let numbers = (1..=64).map(|n| n.to_string()).collect::<Vec<String>>();
+        let input = (1..=65)
+            .map(|id| format!("Card {}: {} | {}", id, numbers.join(" "), numbers.join(" ")))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let cards = parse_input(&input);
+
+        assert_eq!(
+            cascade(&cards[..60]).map(|report| report.total),
+            Ok(u64::MAX >> 4)
+        );
+        assert_eq!(cascade(&cards), Err(OverflowError { card: 65 }));
+        assert_eq!(
+            cascade(&cards[..64]).map(|report| report.total),
+            Ok(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn it_keeps_counting_copies_when_points_overflow() {
+        let numbers = (1..=70).map(|n| n.to_string()).collect::<Vec<String>>();
+        let card = format!("Card 1: {} | {}", numbers.join(" "), numbers.join(" "));
+        let report = cascade(&parse_input(&card)).unwrap();
+
+        assert_eq!(report.cards[0].match_count, 70);
+        assert_eq!(report.cards[0].points, None);
+        assert_eq!(report.total, 1);
+
+        let input = format!("{}\nCard 2: 1 | 2", card);
+        let report = cascade(&parse_input(&input)).unwrap();
+        assert_eq!(report.total, 3);
+        assert_eq!(report.cards[1].points, Some(0));
+    }
+}
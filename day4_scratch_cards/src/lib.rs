@@ -1,3 +1,8 @@
+pub mod card;
+pub mod cascade;
 pub mod consts;
 pub mod part1;
 pub mod part2;
+pub mod scoring;
+pub mod simulator;
+pub mod validation;
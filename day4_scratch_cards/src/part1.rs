@@ -35,57 +35,19 @@
 
 */
 
-use utils::split_and_clean_input_into_lines;
-
-fn winning_numbers_to_score(winning_number_count: usize) -> u32 {
-    if winning_number_count == 0 {
-        return 0;
-    }
-    2_u32.pow(winning_number_count as u32 - 1)
-}
-
-fn get_numbers_from_str(input: &str) -> Vec<u32> {
-    input
-        .trim()
-        .split_whitespace()
-        .map(|val| val.trim().parse::<u32>().unwrap())
-        .collect::<Vec<u32>>()
-}
-
-fn get_winning_and_selected_numbers_from_str(input: &str) -> (Vec<u32>, Vec<u32>) {
-    let (winning_numbers_str, selected_numbers_str) =
-        input.split_once(':').unwrap().1.split_once('|').unwrap();
-
-    let winning_numbers = get_numbers_from_str(winning_numbers_str);
-    let selected_numbers = get_numbers_from_str(selected_numbers_str);
-
-    (winning_numbers, selected_numbers)
-}
-
-fn parse_input(input: &str) -> Vec<(Vec<u32>, Vec<u32>)> {
-    split_and_clean_input_into_lines(input)
-        .iter()
-        .map(|card| {
-            let (winning_numbers, selected_numbers) =
-                get_winning_and_selected_numbers_from_str(card);
-            return (winning_numbers, selected_numbers);
-        })
-        .collect::<Vec<(Vec<u32>, Vec<u32>)>>()
+use crate::{
+    card::parse_input,
+    cascade::OverflowError,
+    scoring::{Doubling, ScoringRule},
+};
+
+/// Scores the cards with any rule, so alternative rule sets can be compared.
+pub fn scratch_cards_with(input: &str, rule: &dyn ScoringRule) -> Result<u64, OverflowError> {
+    rule.score(&parse_input(input))
 }
 
-pub fn scratch_cards(input: &str) -> u32 {
-    let cards = parse_input(input);
-    cards
-        .iter()
-        .map(|(winning_numbers, selected_numbers)| {
-            winning_numbers_to_score(
-                winning_numbers
-                    .iter()
-                    .filter(|val| selected_numbers.contains(val))
-                    .count(),
-            )
-        })
-        .sum()
+pub fn scratch_cards(input: &str) -> u64 {
+    scratch_cards_with(input, &Doubling).expect("Scratch card points should fit in a u64")
 }
 
 #[cfg(test)]
@@ -29,76 +29,18 @@
     Process all of the original and copied scratchcards until no more scratchcards are won. Including the original set of scratchcards, how many total scratchcards do you end up with?
 */
 
-use std::collections::HashMap;
-
-use utils::split_and_clean_input_into_lines;
-
-fn get_numbers_from_str(input: &str) -> Vec<u32> {
-    input
-        .trim()
-        .split_whitespace()
-        .map(|val| val.trim().parse::<u32>().unwrap())
-        .collect::<Vec<u32>>()
+use crate::{
+    card::parse_input,
+    cascade::OverflowError,
+    scoring::{CopiesCappedAtEndOfTable, ScoringRule},
+};
+
+pub fn checked_scratch_cards(input: &str) -> Result<u64, OverflowError> {
+    CopiesCappedAtEndOfTable.score(&parse_input(input))
 }
 
-fn get_winning_and_selected_numbers_from_str(input: &str) -> (u32, Vec<u32>, Vec<u32>) {
-    let (card_metadata, card_data) = input.split_once(':').unwrap();
-    let card_id = card_metadata
-        .split_whitespace()
-        .last()
-        .unwrap()
-        .parse::<u32>()
-        .unwrap();
-    let (winning_numbers_str, selected_numbers_str) = card_data.split_once('|').unwrap();
-
-    let winning_numbers = get_numbers_from_str(winning_numbers_str);
-    let selected_numbers = get_numbers_from_str(selected_numbers_str);
-
-    (card_id, winning_numbers, selected_numbers)
-}
-
-fn parse_input(input: &str) -> Vec<(u32, Vec<u32>, Vec<u32>)> {
-    split_and_clean_input_into_lines(input)
-        .iter()
-        .map(|card| {
-            let (card_id, winning_numbers, selected_numbers) =
-                get_winning_and_selected_numbers_from_str(card);
-            return (card_id, winning_numbers, selected_numbers);
-        })
-        .collect::<Vec<(u32, Vec<u32>, Vec<u32>)>>()
-}
-
-pub fn scratch_cards(input: &str) -> u32 {
-    let cards = parse_input(input);
-    let mut instances_map: HashMap<u32, u32> = HashMap::new();
-    cards
-        .iter()
-        .map(|(card_id, winning_numbers, selected_numbers)| {
-            let winning_numbers_chosen_count = winning_numbers
-                .iter()
-                .filter(|val| selected_numbers.contains(val))
-                .count() as u32;
-
-            let instances = match instances_map.get(card_id) {
-                Some(instances) => *instances,
-                None => 1,
-            };
-
-            for i in 1..(winning_numbers_chosen_count + 1) {
-                let key = card_id + i;
-                if key > cards.len() as u32 {
-                    break;
-                }
-                if let Some(next_instances) = instances_map.get_mut(&key) {
-                    *next_instances += instances;
-                } else {
-                    instances_map.insert(key, 2 + (instances - 1));
-                }
-            }
-
-            instances
-        })
-        .sum()
+pub fn scratch_cards(input: &str) -> u64 {
+    checked_scratch_cards(input).expect("Scratch card total should fit in a u64")
 }
 
 #[cfg(test)]
@@ -120,6 +62,28 @@ mod tests {
         assert_eq!(scratch_cards(input), 30);
     }
 
+    #[test]
+    fn it_works_with_gaps_and_unordered_ids() {
+        let input = r#"
+            Card 10: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 3: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 42:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 7: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+            Card 8: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 8: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        "#;
+        assert_eq!(scratch_cards(input), 30);
+    }
+
+    #[test]
+    fn it_works_with_more_matches_than_points_can_hold() {
+        let numbers = (1..=70).map(|n| n.to_string()).collect::<Vec<String>>();
+        let input = format!("Card 1: {} | {}", numbers.join(" "), numbers.join(" "));
+
+        assert_eq!(checked_scratch_cards(&input), Ok(1));
+        assert_eq!(scratch_cards(&input), 1);
+    }
+
     #[test]
     fn it_works_with_puzzle() {
         assert_eq!(scratch_cards(INPUT), 5554894);
@@ -0,0 +1,158 @@
+use crate::{
+    card::Card,
+    cascade::{cascade, OverflowError},
+};
+
+/// A way of turning a table of scratch cards into a single score.
+pub trait ScoringRule {
+    fn score(&self, cards: &[Card]) -> Result<u64, OverflowError>;
+}
+
+fn sum_points(cards: &[Card], points: impl Fn(usize) -> Option<u64>) -> Result<u64, OverflowError> {
+    cards.iter().try_fold(0_u64, |total, card| {
+        points(card.match_count())
+            .and_then(|points| total.checked_add(points))
+            .ok_or(OverflowError { card: card.id })
+    })
+}
+
+/// Part one: 1 point for the first match, doubled for every other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Doubling;
+
+impl ScoringRule for Doubling {
+    fn score(&self, cards: &[Card]) -> Result<u64, OverflowError> {
+        sum_points(cards, |match_count| match match_count {
+            0 => Some(0),
+            _ => 1_u64.checked_shl(match_count as u32 - 1),
+        })
+    }
+}
+
+/// A fixed number of points per match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear {
+    pub points_per_match: u64,
+}
+
+impl ScoringRule for Linear {
+    fn score(&self, cards: &[Card]) -> Result<u64, OverflowError> {
+        sum_points(cards, |match_count| {
+            self.points_per_match.checked_mul(match_count as u64)
+        })
+    }
+}
+
+/// 1, 2, 3, 5, 8... points for 1, 2, 3, 4, 5... matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fibonacci;
+
+impl ScoringRule for Fibonacci {
+    fn score(&self, cards: &[Card]) -> Result<u64, OverflowError> {
+        sum_points(cards, |match_count| {
+            let (mut current, mut next) = (1_u64, 2_u64);
+            for _ in 1..match_count {
+                (current, next) = (next, current.checked_add(next)?);
+            }
+            Some(if match_count == 0 { 0 } else { current })
+        })
+    }
+}
+
+/// Every match wins a copy of one of the next cards. Copies of cards past
+/// the end of the table are still handed out and counted, but win nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopiesOfNextN;
+
+impl ScoringRule for CopiesOfNextN {
+    fn score(&self, cards: &[Card]) -> Result<u64, OverflowError> {
+        let report = cascade(cards)?;
+        report
+            .cards
+            .iter()
+            .enumerate()
+            .try_fold(report.total, |total, (i, card)| {
+                let past_the_end = (i + card.match_count).saturating_sub(cards.len() - 1);
+                card.instances
+                    .checked_mul(past_the_end as u64)
+                    .and_then(|copies| total.checked_add(copies))
+                    .ok_or(OverflowError { card: card.id })
+            })
+    }
+}
+
+/// Part two: every match wins a copy of one of the next cards, never past
+/// the end of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopiesCappedAtEndOfTable;
+
+impl ScoringRule for CopiesCappedAtEndOfTable {
+    fn score(&self, cards: &[Card]) -> Result<u64, OverflowError> {
+        Ok(cascade(cards)?.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::card::parse_input;
+
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+    "#;
+
+    #[test]
+    fn it_works() {
+        let cards = parse_input(EXAMPLE);
+        let rules: [(&dyn ScoringRule, u64); 5] = [
+            (&Doubling, 13),
+            (
+                &Linear {
+                    points_per_match: 3,
+                },
+                27,
+            ),
+            (&Fibonacci, 5 + 2 + 2 + 1),
+            (&CopiesOfNextN, 30),
+            (&CopiesCappedAtEndOfTable, 30),
+        ];
+
+        for (rule, expected) in rules {
+            assert_eq!(rule.score(&cards), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn it_counts_copies_past_the_end_of_the_table() {
+        // the last two cards win one and three copies past the end
+        let cards = parse_input(
+            r#"
+            Card 1: 1 2 3 | 1 4 5
+            Card 2: 1 2 3 | 1 2 6
+            Card 3: 1 2 3 | 1 2 3
+        "#,
+        );
+
+        assert_eq!(CopiesCappedAtEndOfTable.score(&cards), Ok(1 + 2 + 3));
+        assert_eq!(CopiesOfNextN.score(&cards), Ok(1 + 2 + 3 + 2 + 3 * 3));
+    }
+
+    #[test]
+    fn it_scores_fibonacci_points() {
+        let points = (0..8)
+            .map(|n| {
+                let numbers = (1..=n).map(|n| n.to_string()).collect::<Vec<String>>();
+                let card = format!("Card 1: {} | {}", numbers.join(" "), numbers.join(" "));
+                Fibonacci.score(&parse_input(&card)).unwrap()
+            })
+            .collect::<Vec<u64>>();
+
+        assert_eq!(points, vec![0, 1, 2, 3, 5, 8, 13, 21]);
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use utils::random::Random;
+
+use crate::{
+    cascade::OverflowError, part1::scratch_cards_with, part2::checked_scratch_cards,
+    scoring::Doubling,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationConfig {
+    /// Numbers are drawn from `1..=pool_size`.
+    pub pool_size: u32,
+    /// Distinct winning numbers per card, at most `pool_size`.
+    pub winning_numbers: usize,
+    /// Distinct selected numbers per card, at most `pool_size`.
+    pub selected_numbers: usize,
+    pub cards_per_table: usize,
+    /// Number of tables to simulate, each scored on its own.
+    pub tables: usize,
+}
+
+/// Mean, sample variance and histogram of a simulated quantity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    pub variance: f64,
+    /// How many tables ended up with each value.
+    pub histogram: BTreeMap<u64, usize>,
+}
+
+impl Summary {
+    fn new(values: &[u64]) -> Self {
+        let count = values.len() as f64;
+        let mean = values.iter().map(|&value| value as f64).sum::<f64>() / count;
+        let variance = if values.len() < 2 {
+            0.0
+        } else {
+            values
+                .iter()
+                .map(|&value| (value as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (count - 1.0)
+        };
+
+        let mut histogram: BTreeMap<u64, usize> = BTreeMap::new();
+        for &value in values {
+            *histogram.entry(value).or_default() += 1;
+        }
+
+        Self {
+            mean,
+            variance,
+            histogram,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    /// Part one points of a table.
+    pub points: Summary,
+    /// Part two scratch cards of a table, originals and copies.
+    pub total_cards: Summary,
+}
+
+fn draw_numbers(random: &mut Random, pool: &mut [u32], count: usize) -> String {
+    random.shuffle(pool);
+    pool[..count]
+        .iter()
+        .map(|number| format!("{:>2}", number))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// A table of random cards, in the puzzle input format.
+pub fn generate_table(random: &mut Random, config: &SimulationConfig) -> String {
+    assert!(
+        config.winning_numbers <= config.pool_size as usize
+            && config.selected_numbers <= config.pool_size as usize,
+        "Cards should not hold more numbers than the pool"
+    );
+
+    let mut pool = (1..=config.pool_size).collect::<Vec<u32>>();
+    (1..=config.cards_per_table)
+        .map(|id| {
+            let winning_numbers = draw_numbers(random, &mut pool, config.winning_numbers);
+            let selected_numbers = draw_numbers(random, &mut pool, config.selected_numbers);
+            format!("Card {}: {} | {}", id, winning_numbers, selected_numbers)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Generates and scores `config.tables` tables with the part one and part
+/// two rules.
+pub fn simulate(seed: u64, config: &SimulationConfig) -> Result<SimulationReport, OverflowError> {
+    assert!(config.tables > 0, "At least one table should be simulated");
+
+    let mut random = Random::new(seed);
+    let mut points: Vec<u64> = Vec::with_capacity(config.tables);
+    let mut total_cards: Vec<u64> = Vec::with_capacity(config.tables);
+    for _ in 0..config.tables {
+        let table = generate_table(&mut random, config);
+        points.push(scratch_cards_with(&table, &Doubling)?);
+        total_cards.push(checked_scratch_cards(&table)?);
+    }
+
+    Ok(SimulationReport {
+        points: Summary::new(&points),
+        total_cards: Summary::new(&total_cards),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validation::validate;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let config = SimulationConfig {
+            pool_size: 99,
+            winning_numbers: 10,
+            selected_numbers: 25,
+            cards_per_table: 20,
+            tables: 200,
+        };
+        let report = simulate(1, &config).unwrap();
+
+        assert_eq!(report, simulate(1, &config).unwrap());
+        assert_ne!(report, simulate(2, &config).unwrap());
+        assert_eq!(report.points.histogram.values().sum::<usize>(), 200);
+        assert!(report.total_cards.mean >= 20.0);
+
+        let table = generate_table(&mut Random::new(1), &config);
+        assert_eq!(table.lines().count(), 20);
+        assert_eq!(validate(&table), vec![]);
+    }
+
+    #[test]
+    fn it_handles_certain_outcomes() {
+        // every number is both winning and selected, cards match everything
+        let config = SimulationConfig {
+            pool_size: 3,
+            winning_numbers: 3,
+            selected_numbers: 3,
+            cards_per_table: 4,
+            tables: 10,
+        };
+        let report = simulate(0, &config).unwrap();
+
+        assert_eq!(report.points.mean, 16.0);
+        assert_eq!(report.points.variance, 0.0);
+        assert_eq!(report.points.histogram, BTreeMap::from([(16, 10)]));
+        assert_eq!(report.total_cards.mean, 1.0 + 2.0 + 4.0 + 8.0);
+    }
+
+    #[test]
+    fn it_converges_to_the_expected_value() {
+        // a single number out of ten matches one time in ten
+        let config = SimulationConfig {
+            pool_size: 10,
+            winning_numbers: 1,
+            selected_numbers: 1,
+            cards_per_table: 1,
+            tables: 20_000,
+        };
+        let report = simulate(3, &config).unwrap();
+
+        assert!((report.points.mean - 0.1).abs() < 0.01);
+        assert!((report.points.variance - 0.09).abs() < 0.01);
+        assert_eq!(report.total_cards.histogram, BTreeMap::from([(1, 20_000)]));
+    }
+}
@@ -0,0 +1,222 @@
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anomaly {
+    /// The line is not `Card <id>: <numbers> | <numbers>`.
+    Malformed,
+    /// The number is listed again among the winning numbers, it matches once
+    /// per listing.
+    DuplicateWinningNumber(u32),
+    /// The ID is lower than the one of the card above it.
+    OutOfOrderId { previous: u32 },
+    /// The ID was already used on an earlier line.
+    DuplicatedId { first_line: usize },
+    /// The number of winning or selected numbers differs from the first card.
+    InconsistentColumns {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// Line of the card in the input, counting from 1 and blank lines
+    /// included.
+    pub line: usize,
+    /// `None` when the ID itself could not be read.
+    pub card: Option<u32>,
+    pub anomaly: Anomaly,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if let Some(card) = self.card {
+            write!(f, " (card {})", card)?;
+        }
+        match &self.anomaly {
+            Anomaly::Malformed => write!(f, ": malformed card"),
+            Anomaly::DuplicateWinningNumber(number) => {
+                write!(f, ": {} is listed twice in the winning numbers", number)
+            }
+            Anomaly::OutOfOrderId { previous } => {
+                write!(f, ": comes after card {}", previous)
+            }
+            Anomaly::DuplicatedId { first_line } => {
+                write!(f, ": ID already used on line {}", first_line)
+            }
+            Anomaly::InconsistentColumns { expected, found } => write!(
+                f,
+                ": {} winning and {} selected numbers, expected {} and {}",
+                found.0, found.1, expected.0, expected.1
+            ),
+        }
+    }
+}
+
+fn parse_numbers(input: &str) -> Option<Vec<u32>> {
+    input
+        .split_whitespace()
+        .map(|val| val.parse::<u32>().ok())
+        .collect()
+}
+
+// winning and selected numbers
+type Numbers = (Vec<u32>, Vec<u32>);
+
+fn parse_card(line: &str) -> (Option<u32>, Option<Numbers>) {
+    let Some((card_metadata, card_data)) = line.split_once(':') else {
+        return (None, None);
+    };
+    let id = card_metadata
+        .strip_prefix("Card")
+        .and_then(|id| id.trim().parse::<u32>().ok());
+    let numbers = card_data
+        .split_once('|')
+        .and_then(|(winning, selected)| Some((parse_numbers(winning)?, parse_numbers(selected)?)));
+    (id, numbers)
+}
+
+/// Reports every anomaly of a card table, in line order. The solvers never
+/// rely on IDs, so a table with anomalies can still be scored.
+pub fn validate(input: &str) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = vec![];
+    let mut first_lines: HashMap<u32, usize> = HashMap::new();
+    let mut previous_id: Option<u32> = None;
+    let mut expected_columns: Option<(usize, usize)> = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let line_number = i + 1;
+        let (card, numbers) = parse_card(line);
+        let mut report = |anomaly: Anomaly| {
+            issues.push(Issue {
+                line: line_number,
+                card,
+                anomaly,
+            })
+        };
+
+        let (Some(id), Some((winning_numbers, selected_numbers))) = (card, numbers) else {
+            report(Anomaly::Malformed);
+            continue;
+        };
+
+        match first_lines.get(&id) {
+            Some(&first_line) => report(Anomaly::DuplicatedId { first_line }),
+            None => {
+                first_lines.insert(id, line_number);
+            }
+        }
+        if let Some(previous) = previous_id.filter(|&previous| id < previous) {
+            report(Anomaly::OutOfOrderId { previous });
+        }
+        previous_id = Some(id);
+
+        let mut seen: Vec<u32> = vec![];
+        for number in winning_numbers.iter() {
+            if seen.contains(number) {
+                report(Anomaly::DuplicateWinningNumber(*number));
+            } else {
+                seen.push(*number);
+            }
+        }
+
+        let found = (winning_numbers.len(), selected_numbers.len());
+        let expected = *expected_columns.get_or_insert(found);
+        if found != expected {
+            report(Anomaly::InconsistentColumns { expected, found });
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let input = r#"
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+        "#;
+
+        assert_eq!(validate(input), vec![]);
+    }
+
+    #[test]
+    fn it_reports_anomalies() {
+        let input = r#"
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 3: 13 32 13 16 61 | 61 30 68 82 17 32 24 19
+            Card 2:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 3: 41 92 73 84 | 59 84 76 51 58  5 54 83
+            Card x: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 6: 31 18 13 56 72 74 77 10 23 35 67 36 11
+        "#;
+        let issues = validate(input);
+
+        assert_eq!(
+            issues,
+            vec![
+                Issue {
+                    line: 3,
+                    card: Some(3),
+                    anomaly: Anomaly::DuplicateWinningNumber(13),
+                },
+                Issue {
+                    line: 4,
+                    card: Some(2),
+                    anomaly: Anomaly::OutOfOrderId { previous: 3 },
+                },
+                Issue {
+                    line: 5,
+                    card: Some(3),
+                    anomaly: Anomaly::DuplicatedId { first_line: 3 },
+                },
+                Issue {
+                    line: 5,
+                    card: Some(3),
+                    anomaly: Anomaly::InconsistentColumns {
+                        expected: (5, 8),
+                        found: (4, 8),
+                    },
+                },
+                Issue {
+                    line: 6,
+                    card: None,
+                    anomaly: Anomaly::Malformed,
+                },
+                Issue {
+                    line: 7,
+                    card: Some(6),
+                    anomaly: Anomaly::Malformed,
+                },
+            ]
+        );
+        assert_eq!(
+            issues[3].to_string(),
+            "line 5 (card 3): 4 winning and 8 selected numbers, expected 5 and 8"
+        );
+    }
+
+    #[test]
+    fn it_reports_lines_of_the_input() {
+        let input = "\n\nCard 1: 1 2 | 3\n\n  \nCard 1: 4 5 | 6\n";
+
+        assert_eq!(
+            validate(input),
+            vec![Issue {
+                line: 6,
+                card: Some(1),
+                anomaly: Anomaly::DuplicatedId { first_line: 3 },
+            }]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Neg, Sub},
+    str::FromStr,
+};
+
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_WIDTH: usize = 9;
+
+/// A minimal arbitrary-precision signed integer supporting what the
+/// difference engine needs: parsing, addition, subtraction and printing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    is_negative: bool,
+    // base 2^32 limbs, least significant first, without trailing zeros
+    magnitude: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid digit found in big integer literal")
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+fn trim(magnitude: &mut Vec<u32>) {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+// expects |a| >= |b|
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut difference = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if difference < 0 {
+            difference += 1 << 32;
+            borrow = 1;
+        }
+        result.push(difference as u32);
+    }
+    trim(&mut result);
+    result
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.is_negative
+    }
+
+    fn from_parts(is_negative: bool, mut magnitude: Vec<u32>) -> Self {
+        trim(&mut magnitude);
+        Self {
+            is_negative: is_negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    fn mul_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in self.magnitude.iter_mut() {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry > 0 {
+            self.magnitude.push(carry as u32);
+        }
+    }
+
+    // divides the magnitude in place and returns the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.magnitude.iter_mut().rev() {
+            let current = (remainder << 32) | *limb as u64;
+            *limb = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        trim(&mut self.magnitude);
+        remainder as u32
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut limbs = vec![];
+        while magnitude > 0 {
+            limbs.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        Self::from_parts(value < 0, limbs)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        Self::from(value as i128)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (is_negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(ParseBigIntError);
+        }
+
+        let mut value = BigInt::zero();
+        for c in digits.chars() {
+            let digit = c.to_digit(10).ok_or(ParseBigIntError)?;
+            value.mul_add_small(10, digit);
+        }
+        Ok(Self::from_parts(is_negative, value.magnitude))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut value = self.clone();
+        let mut chunks = vec![];
+        while !value.is_zero() {
+            chunks.push(value.div_rem_small(DECIMAL_CHUNK));
+        }
+
+        if self.is_negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:0width$}", chunk, width = DECIMAL_CHUNK_WIDTH)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative, other.is_negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        Self::from_parts(!self.is_negative, self.magnitude)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: Self) -> Self::Output {
+        if self.is_negative == other.is_negative {
+            return BigInt::from_parts(
+                self.is_negative,
+                add_magnitude(&self.magnitude, &other.magnitude),
+            );
+        }
+
+        match cmp_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Equal => BigInt::zero(),
+            Ordering::Greater => BigInt::from_parts(
+                self.is_negative,
+                sub_magnitude(&self.magnitude, &other.magnitude),
+            ),
+            Ordering::Less => BigInt::from_parts(
+                other.is_negative,
+                sub_magnitude(&other.magnitude, &self.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + &-other.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_through_strings() {
+        for literal in [
+            "0",
+            "-1",
+            "4294967296",
+            "-18446744073709551616",
+            "123456789012345678901234567890",
+        ] {
+            assert_eq!(literal.parse::<BigInt>().unwrap().to_string(), literal);
+        }
+        assert_eq!("-0".parse::<BigInt>().unwrap(), BigInt::zero());
+        assert_eq!("12a".parse::<BigInt>(), Err(ParseBigIntError));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError));
+    }
+
+    #[test]
+    fn it_adds_and_subtracts() {
+        let max = BigInt::from(i128::MAX);
+        let min = BigInt::from(i128::MIN);
+
+        assert_eq!(
+            (&max + &BigInt::from(1i64)).to_string(),
+            "170141183460469231731687303715884105728"
+        );
+        assert_eq!(
+            (&min - &BigInt::from(1i64)).to_string(),
+            "-170141183460469231731687303715884105729"
+        );
+        assert_eq!(&max + &min, BigInt::from(-1i64));
+        assert_eq!(
+            &BigInt::from(5i64) - &BigInt::from(8i64),
+            BigInt::from(-3i64)
+        );
+        assert!(BigInt::from(-8i64) < BigInt::from(-3i64));
+    }
+}
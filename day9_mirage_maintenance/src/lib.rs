@@ -1,3 +1,7 @@
+pub mod bigint;
 pub mod consts;
+pub mod numeric;
 pub mod part1;
 pub mod part2;
+pub mod polynomial;
+pub mod rational;
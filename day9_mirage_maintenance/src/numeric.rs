@@ -0,0 +1,82 @@
+use std::{fmt, num::IntErrorKind};
+
+use crate::bigint::BigInt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arithmetic overflow while extrapolating history")
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+/// The arithmetic the difference engine relies on. Fixed-width types report
+/// overflow (including out of range literals) by returning `None`,
+/// arbitrary-precision types never do.
+pub trait Integer: Clone + PartialEq {
+    fn zero() -> Self;
+    fn parse_checked(literal: &str) -> Option<Self>;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+macro_rules! impl_fixed_width_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn parse_checked(literal: &str) -> Option<Self> {
+                    match literal.parse::<$t>() {
+                        Ok(value) => Some(value),
+                        Err(err) => match err.kind() {
+                            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => None,
+                            _ => panic!("Invalid integer {}: {}", literal, err),
+                        },
+                    }
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_width_integer!(i32, i64, i128);
+
+impl Integer for BigInt {
+    fn zero() -> Self {
+        BigInt::zero()
+    }
+
+    fn parse_checked(literal: &str) -> Option<Self> {
+        Some(literal.parse().unwrap())
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn is_zero(&self) -> bool {
+        BigInt::is_zero(self)
+    }
+}
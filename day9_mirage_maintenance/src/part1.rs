@@ -1,30 +1,32 @@
 use utils::split_and_clean_input_into_lines;
 
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
+use crate::numeric::{Integer, OverflowError};
+
+fn parse_input<T: Integer>(input: &str) -> Result<Vec<Vec<T>>, OverflowError> {
     split_and_clean_input_into_lines(input)
         .iter()
         .map(|line| {
             line.split_whitespace()
-                .map(|val| val.trim().parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
+                .map(|val| T::parse_checked(val).ok_or(OverflowError))
+                .collect::<Result<Vec<T>, OverflowError>>()
         })
-        .collect::<Vec<Vec<i32>>>()
+        .collect::<Result<Vec<Vec<T>>, OverflowError>>()
 }
 
-fn solve_history(initial_values: &Vec<i32>) -> i32 {
-    let mut levels: Vec<Vec<i32>> = vec![initial_values.clone()];
+fn solve_history<T: Integer>(initial_values: &[T]) -> Result<T, OverflowError> {
+    let mut levels: Vec<Vec<T>> = vec![initial_values.to_vec()];
     loop {
         let last_level = levels.last().unwrap();
-        let mut new_level: Vec<i32> = vec![];
+        let mut new_level: Vec<T> = vec![];
 
-        for i in 1..last_level.len() {
-            let previous = last_level.get(i - 1).unwrap();
-            let current = last_level.get(i).unwrap();
+        for pair in last_level.windows(2) {
+            let previous = &pair[0];
+            let current = &pair[1];
 
-            new_level.push(current - previous);
+            new_level.push(current.checked_sub(previous).ok_or(OverflowError)?);
         }
 
-        let is_only_zeros = new_level.iter().all(|&v| v == 0);
+        let is_only_zeros = new_level.iter().all(|v| v.is_zero());
 
         levels.push(new_level);
 
@@ -33,22 +35,34 @@ fn solve_history(initial_values: &Vec<i32>) -> i32 {
         }
     }
 
+    // histories that never reach a level of zeros end with an empty level
     levels
         .iter()
-        .fold(0, |acc, level| acc + level.last().unwrap())
+        .filter_map(|level| level.last())
+        .try_fold(T::zero(), |acc, value| {
+            acc.checked_add(value).ok_or(OverflowError)
+        })
 }
 
-pub fn solve(input: &str) -> i32 {
-    parse_input(input)
+pub fn solve_as<T: Integer>(input: &str) -> Result<T, OverflowError> {
+    parse_input::<T>(input)?
         .iter()
-        .map(|history| solve_history(history))
-        .sum()
+        .try_fold(T::zero(), |acc, history| {
+            acc.checked_add(&solve_history(history)?)
+                .ok_or(OverflowError)
+        })
+}
+
+pub fn solve(input: &str) -> i64 {
+    solve_as::<i64>(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::consts::INPUT;
 
+    use crate::bigint::BigInt;
+
     use super::*;
 
     #[test]
@@ -75,4 +89,35 @@ mod tests {
     fn it_works_with_puzzle() {
         assert_eq!(solve(INPUT), 1731106378);
     }
+
+    #[test]
+    fn it_reports_overflow_for_fixed_width_types() {
+        let input = r#"
+            0 4611686018427387904 9223372036854775807
+        "#;
+
+        assert_eq!(solve_as::<i64>(input), Err(OverflowError));
+        assert_eq!(solve_as::<i128>(input), Ok(13835058055282163709));
+        assert_eq!(
+            solve_as::<BigInt>(input).unwrap().to_string(),
+            "13835058055282163709"
+        );
+    }
+
+    #[test]
+    fn it_works_with_big_integers() {
+        let input = r#"
+            170141183460469231731687303715884105727 340282366920938463463374607431768211454
+        "#;
+
+        assert_eq!(solve_as::<i128>(input), Err(OverflowError));
+        assert_eq!(
+            solve_as::<BigInt>(input).unwrap().to_string(),
+            "510423550381407695195061911147652317181"
+        );
+        assert_eq!(
+            solve_as::<BigInt>(INPUT).unwrap(),
+            BigInt::from(1731106378i64)
+        );
+    }
 }
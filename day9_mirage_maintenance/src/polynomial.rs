@@ -0,0 +1,233 @@
+use std::fmt;
+
+use utils::split_and_clean_input_into_lines;
+
+use crate::{numeric::OverflowError, rational::Rational};
+
+/// The unique minimal-degree polynomial going through every value of a history,
+/// where the value at index `n` of the history is `p(n)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    // coefficients[k] is the coefficient of n^k, without trailing zeros
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    /// Fits the history exactly, an error when an intermediate value does not
+    /// fit in an `i128`.
+    pub fn fit(history: &[i64]) -> Result<Self, OverflowError> {
+        // leading forward differences: Δ^k y(0)
+        let mut leading_differences: Vec<i128> = vec![];
+        let mut level: Vec<i128> = history.iter().map(|&v| v as i128).collect();
+        while !level.is_empty() {
+            leading_differences.push(level[0]);
+            level = level
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]).ok_or(OverflowError))
+                .collect::<Result<Vec<i128>, OverflowError>>()?;
+        }
+        // higher binomials only add zeros, yet their k! denominators overflow
+        while leading_differences.last() == Some(&0) {
+            leading_differences.pop();
+        }
+
+        // Newton form: p(n) = Σ Δ^k y(0) * C(n, k)
+        // with C(n, k + 1) = C(n, k) * (n - k) / (k + 1)
+        let mut coefficients: Vec<Rational> = vec![];
+        let mut binomial: Vec<Rational> = vec![Rational::ONE];
+        for (k, &difference) in leading_differences.iter().enumerate() {
+            if coefficients.len() < binomial.len() {
+                coefficients.resize(binomial.len(), Rational::ZERO);
+            }
+            let difference = Rational::from_integer(difference);
+            for (degree, &coefficient) in binomial.iter().enumerate() {
+                coefficients[degree] = difference
+                    .checked_mul(coefficient)
+                    .and_then(|term| coefficients[degree].checked_add(term))
+                    .ok_or(OverflowError)?;
+            }
+            if k + 1 == leading_differences.len() {
+                break;
+            }
+
+            let scale = Rational::new(1, k as i128 + 1);
+            let shift = Rational::from_integer(k as i128);
+            let mut next_binomial = vec![Rational::ZERO; binomial.len() + 1];
+            for (degree, &coefficient) in binomial.iter().enumerate() {
+                let scaled = coefficient.checked_mul(scale).ok_or(OverflowError)?;
+                next_binomial[degree + 1] = next_binomial[degree + 1]
+                    .checked_add(scaled)
+                    .ok_or(OverflowError)?;
+                next_binomial[degree] = scaled
+                    .checked_mul(shift)
+                    .and_then(|term| next_binomial[degree].checked_sub(term))
+                    .ok_or(OverflowError)?;
+            }
+            binomial = next_binomial;
+        }
+
+        while coefficients.last().is_some_and(|c| c.is_zero()) {
+            coefficients.pop();
+        }
+
+        Ok(Self { coefficients })
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    /// Evaluates `p(n)` using Horner's method, returns `None` on overflow.
+    pub fn evaluate(&self, n: i64) -> Option<Rational> {
+        let n = Rational::from_integer(n as i128);
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(Rational::ZERO, |acc, &coefficient| {
+                acc.checked_mul(n)?.checked_add(coefficient)
+            })
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coefficients.is_empty() {
+            return write!(f, "0");
+        }
+
+        let mut is_first = true;
+        for (degree, coefficient) in self.coefficients.iter().enumerate().rev() {
+            if coefficient.is_zero() {
+                continue;
+            }
+
+            let is_negative = coefficient.numerator() < 0;
+            match (is_first, is_negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            is_first = false;
+
+            let magnitude = coefficient.abs();
+            if degree == 0 {
+                write!(f, "{}", magnitude)?;
+                continue;
+            }
+            if magnitude != Rational::ONE {
+                write!(f, "{} ", magnitude)?;
+            }
+            if degree == 1 {
+                write!(f, "n")?;
+            } else {
+                write!(f, "n^{}", degree)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn fit_input(input: &str) -> Result<Vec<Polynomial>, OverflowError> {
+    split_and_clean_input_into_lines(input)
+        .iter()
+        .map(|line| {
+            let history = line
+                .split_whitespace()
+                .map(|val| val.parse::<i64>().unwrap())
+                .collect::<Vec<i64>>();
+            Polynomial::fit(&history)
+        })
+        .collect::<Result<Vec<Polynomial>, OverflowError>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::consts::INPUT;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let polynomials = fit_input(
+            r#"
+            0 3 6 9 12 15
+            1 3 6 10 15 21
+            10 13 16 21 30 45
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(polynomials[0].to_string(), "3 n");
+        assert_eq!(polynomials[1].to_string(), "1/2 n^2 + 3/2 n + 1");
+        assert_eq!(polynomials[2].to_string(), "1/3 n^3 - n^2 + 11/3 n + 10");
+        assert_eq!(polynomials[2].degree(), 3);
+        assert_eq!(polynomials[2].evaluate(6), Some(Rational::from_integer(68)));
+        assert_eq!(polynomials[2].evaluate(-1), Some(Rational::from_integer(5)));
+    }
+
+    #[test]
+    fn it_evaluates_far_future_indices() {
+        let polynomial = Polynomial::fit(&[1, 3, 6, 10, 15, 21]).unwrap();
+
+        assert_eq!(
+            polynomial.evaluate(1_000_000_000),
+            Some(Rational::from_integer(500_000_001_500_000_001))
+        );
+    }
+
+    #[test]
+    fn it_handles_degenerate_histories() {
+        assert_eq!(Polynomial::fit(&[]).unwrap().to_string(), "0");
+        assert_eq!(Polynomial::fit(&[0, 0, 0]).unwrap().to_string(), "0");
+        assert_eq!(Polynomial::fit(&[-4, -4]).unwrap().to_string(), "-4");
+        assert_eq!(Polynomial::fit(&[2, 1, 0]).unwrap().to_string(), "-n + 2");
+    }
+
+    #[test]
+    fn it_reports_overflow_on_extreme_histories() {
+        let alternating = (0..80)
+            .map(|i| if i % 2 == 0 { i64::MAX } else { i64::MIN })
+            .collect::<Vec<i64>>();
+        assert_eq!(Polynomial::fit(&alternating), Err(OverflowError));
+        assert!(Polynomial::fit(&[i64::MIN, i64::MAX, i64::MIN, i64::MAX]).is_ok());
+
+        // a long constant history needs no high degree binomial
+        let constant = Polynomial::fit(&[i64::MIN; 100]).unwrap();
+        assert_eq!(constant.degree(), 0);
+        assert_eq!(
+            constant.evaluate(1_000),
+            Some(Rational::from_integer(i64::MIN as i128))
+        );
+    }
+
+    #[test]
+    fn it_works_with_puzzle() {
+        let polynomials = fit_input(INPUT).unwrap();
+        let history_len = INPUT
+            .trim()
+            .lines()
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .count() as i64;
+
+        let next: Rational = polynomials
+            .iter()
+            .map(|p| p.evaluate(history_len).unwrap())
+            .try_fold(Rational::ZERO, |acc, v| acc.checked_add(v))
+            .unwrap();
+        let previous: Rational = polynomials
+            .iter()
+            .map(|p| p.evaluate(-1).unwrap())
+            .try_fold(Rational::ZERO, |acc, v| acc.checked_add(v))
+            .unwrap();
+
+        assert_eq!(next, Rational::from_integer(1731106378));
+        assert_eq!(previous, Rational::from_integer(1087));
+    }
+}
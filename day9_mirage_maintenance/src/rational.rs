@@ -0,0 +1,145 @@
+use std::fmt;
+
+// unsigned so that `i128::MIN` has a magnitude, the denominator being positive
+// keeps the result in range
+fn gcd(a: i128, b: i128) -> i128 {
+    let mut a = a.unsigned_abs();
+    let mut b = b.unsigned_abs();
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+/// An exact fraction, always kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Rational = Rational {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "Denominator should never be zero");
+
+        let divisor = gcd(numerator, denominator);
+        let sign = denominator.signum();
+        Self {
+            numerator: sign * numerator / divisor,
+            denominator: sign * denominator / divisor,
+        }
+    }
+
+    pub fn from_integer(value: i128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator,
+        }
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let divisor = gcd(self.denominator, other.denominator);
+        let left = self.numerator.checked_mul(other.denominator / divisor)?;
+        let right = other.numerator.checked_mul(self.denominator / divisor)?;
+        let denominator = (self.denominator / divisor).checked_mul(other.denominator)?;
+        Some(Self::new(left.checked_add(right)?, denominator))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // cross reduce first so intermediate products stay as small as possible
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        let numerator = (self.numerator / a).checked_mul(other.numerator / b)?;
+        let denominator = (self.denominator / b).checked_mul(other.denominator / a)?;
+        Some(Self::new(numerator, denominator))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_normalizes() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!(Rational::new(0, -7), Rational::ZERO);
+        assert_eq!(Rational::new(-3, 2).to_string(), "-3/2");
+        assert_eq!(Rational::new(8, 4).to_string(), "2");
+    }
+
+    #[test]
+    fn it_does_arithmetic() {
+        let half = Rational::new(1, 2);
+        let third = Rational::new(1, 3);
+
+        assert_eq!(half.checked_add(third), Some(Rational::new(5, 6)));
+        assert_eq!(half.checked_sub(third), Some(Rational::new(1, 6)));
+        assert_eq!(half.checked_mul(third), Some(Rational::new(1, 6)));
+        assert_eq!(
+            Rational::from_integer(i128::MAX).checked_add(Rational::ONE),
+            None
+        );
+        assert_eq!(
+            Rational::from_integer(i128::MAX).checked_mul(Rational::from_integer(2)),
+            None
+        );
+        assert_eq!(Rational::from_integer(i128::MIN).checked_neg(), None);
+        assert_eq!(
+            Rational::ZERO.checked_sub(Rational::from_integer(i128::MIN)),
+            None
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub mod random;
+
 pub fn split_and_clean_input_into_lines(input: &str) -> Vec<&str> {
     input
         .trim()